@@ -1,16 +1,29 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, ConfigError>;
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum ConfigError {
-    #[error("failed to read configuration file: {0}")]
-    Io(String),
+    #[error("failed to access {}: {}", .0.display(), .1)]
+    Io(PathBuf, String),
+
+    #[error("file not found: {}", .0.display())]
+    NotFound(PathBuf),
+
+    #[error("permission denied: {}", .0.display())]
+    PermissionDenied(PathBuf),
 
     #[error("previous write failed: {0}")]
     FailedWrite(String),
 
+    #[error("written data does not match the config: {0}")]
+    VerificationFailed(String),
+
     #[error("failed to serialize {0} data: {1}")]
     Serialization(String, String),
 
@@ -21,13 +34,16 @@ pub enum ConfigError {
     NoHomeDir,
 }
 
-impl From<std::io::Error> for ConfigError {
-    fn from(value: std::io::Error) -> Self {
-        ConfigError::Io(value.to_string())
+impl ConfigError {
+    #[must_use]
+    pub fn io(path: &Path, error: &std::io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::NotFound => ConfigError::NotFound(path.to_path_buf()),
+            ErrorKind::PermissionDenied => ConfigError::PermissionDenied(path.to_path_buf()),
+            _ => ConfigError::Io(path.to_path_buf(), error.to_string()),
+        }
     }
-}
 
-impl ConfigError {
     pub fn serialization(format: &'static str, error: impl Display) -> Self {
         ConfigError::Serialization(format.into(), error.to_string())
     }
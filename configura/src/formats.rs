@@ -46,6 +46,13 @@ mod toml_impl {
 
     pub struct TomlFormat;
 
+    /// Options for [`TomlFormat`], set `type FormatContext = TomlContext` in the config to use them.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct TomlContext {
+        /// Put each array element on its own line even when the output is not pretty, which is how the file is saved.
+        pub pretty_array: bool,
+    }
+
     impl Format<()> for TomlFormat {
         const EXTENSION: &'static str = "toml";
 
@@ -55,15 +62,42 @@ mod toml_impl {
         where
             T: Serialize,
         {
-            if pretty {
+            <Self as Format<TomlContext>>::to_string(data, pretty, None)
+        }
+
+        fn from_reader<R, T>(reader: R, _context: Option<&()>) -> crate::Result<T>
+        where
+            R: Read,
+            T: DeserializeOwned,
+        {
+            <Self as Format<TomlContext>>::from_reader(reader, None)
+        }
+    }
+
+    impl Format<TomlContext> for TomlFormat {
+        const EXTENSION: &'static str = "toml";
+
+        type FormatContext = TomlContext;
+
+        fn to_string<T>(
+            data: &T,
+            pretty: bool,
+            context: Option<&TomlContext>,
+        ) -> crate::Result<String>
+        where
+            T: Serialize,
+        {
+            let pretty_array = context.is_some_and(|c| c.pretty_array);
+
+            if pretty || pretty_array {
                 to_string_pretty(data)
             } else {
                 to_string(data)
             }
-            .map_err(|e| ConfigError::serialization(Self::EXTENSION, e))
+            .map_err(|e| ConfigError::serialization("toml", e))
         }
 
-        fn from_reader<R, T>(reader: R, _context: Option<&()>) -> crate::Result<T>
+        fn from_reader<R, T>(reader: R, _context: Option<&TomlContext>) -> crate::Result<T>
         where
             R: Read,
             T: DeserializeOwned,
@@ -71,34 +105,43 @@ mod toml_impl {
             let mut buffer = String::new();
             let mut buf_reader = BufReader::new(reader);
 
-            buf_reader.read_to_string(&mut buffer)?;
-            from_str(&buffer).map_err(|e| ConfigError::deserialization(Self::EXTENSION, e))
+            buf_reader
+                .read_to_string(&mut buffer)
+                .map_err(|e| ConfigError::deserialization("toml", e))?;
+            from_str(&buffer).map_err(|e| ConfigError::deserialization("toml", e))
         }
     }
 }
 
 #[cfg(feature = "toml")]
-pub use toml_impl::TomlFormat;
+pub use toml_impl::{TomlContext, TomlFormat};
 
 #[cfg(feature = "yaml")]
 mod yaml_impl {
     use crate::{Format, errors::ConfigError};
     use serde::{Serialize, de::DeserializeOwned};
-    use serde_yml::{from_reader, to_string};
+    use serde_yml::{Serializer, from_reader, ser::SerializerConfig};
     use std::io::Read;
 
     pub struct YamlFormat;
 
+    /// Options for [`YamlFormat`], set `type FormatContext = YamlContext` in the config to use them.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct YamlContext {
+        /// Serialize unit enum variants as tags, e.g. `!Unit` instead of `Unit`.
+        pub tag_unit_variants: bool,
+    }
+
     impl Format<()> for YamlFormat {
         const EXTENSION: &'static str = "yaml";
 
         type FormatContext = ();
 
-        fn to_string<T>(data: &T, _pretty: bool, _context: Option<&()>) -> crate::Result<String>
+        fn to_string<T>(data: &T, pretty: bool, _context: Option<&()>) -> crate::Result<String>
         where
             T: Serialize,
         {
-            to_string(data).map_err(|e| ConfigError::serialization(Self::EXTENSION, e))
+            <Self as Format<YamlContext>>::to_string(data, pretty, None)
         }
 
         fn from_reader<R, T>(reader: R, _context: Option<&()>) -> crate::Result<T>
@@ -106,10 +149,46 @@ mod yaml_impl {
             R: Read,
             T: DeserializeOwned,
         {
-            from_reader(reader).map_err(|e| ConfigError::deserialization(Self::EXTENSION, e))
+            <Self as Format<YamlContext>>::from_reader(reader, None)
+        }
+    }
+
+    impl Format<YamlContext> for YamlFormat {
+        const EXTENSION: &'static str = "yaml";
+
+        type FormatContext = YamlContext;
+
+        fn to_string<T>(
+            data: &T,
+            _pretty: bool,
+            context: Option<&YamlContext>,
+        ) -> crate::Result<String>
+        where
+            T: Serialize,
+        {
+            let config = SerializerConfig {
+                tag_unit_variants: context.is_some_and(|c| c.tag_unit_variants),
+            };
+            let mut buffer = Vec::new();
+
+            {
+                let mut serializer = Serializer::new_with_config(&mut buffer, config);
+                data.serialize(&mut serializer)
+                    .map_err(|e| ConfigError::serialization("yaml", e))?;
+            }
+
+            String::from_utf8(buffer).map_err(|e| ConfigError::serialization("yaml", e))
+        }
+
+        fn from_reader<R, T>(reader: R, _context: Option<&YamlContext>) -> crate::Result<T>
+        where
+            R: Read,
+            T: DeserializeOwned,
+        {
+            from_reader(reader).map_err(|e| ConfigError::deserialization("yaml", e))
         }
     }
 }
 
 #[cfg(feature = "yaml")]
-pub use yaml_impl::YamlFormat;
+pub use yaml_impl::{YamlContext, YamlFormat};
@@ -0,0 +1,157 @@
+use crate::errors::{ConfigError, Result};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// A config field that keeps its raw data and only deserializes it into `T` the first time it's accessed, useful for
+/// large sections that are rarely needed.
+///
+/// The raw data is stored as a [`serde_json::Value`] regardless of the config format, so it requires the `json` feature.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use configura::{Config, formats::JsonFormat, lazy::Lazy};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Default, Serialize, Deserialize, PartialEq)]
+/// struct MyConfig {
+///     name: String,
+///     words: Lazy<Vec<String>>,
+/// }
+///
+/// impl Config for MyConfig {
+///     type FormatType = JsonFormat;
+///     type FormatContext = ();
+///
+///     fn config_path_and_filename(_home_dir: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+///         (None, "my_config")
+///     }
+/// }
+///
+/// let config: MyConfig = configura::load_config().unwrap();
+/// let words = config.words.get().unwrap(); // parsed here
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lazy<T> {
+    raw: Value,
+    value: OnceLock<T>,
+}
+
+impl<T> Lazy<T> {
+    /// Creates a new [Lazy] from a value, the value is serialized right away so it can be saved with the config
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to store
+    ///
+    /// ## Returns
+    ///
+    /// A new [Lazy] that is already loaded
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConfigError::Serialization`]: The value could not be serialized
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use configura::lazy::Lazy;
+    ///
+    /// let words = Lazy::new(vec!["hello".to_string()]).unwrap();
+    /// ```
+    pub fn new(value: T) -> Result<Self>
+    where
+        T: Serialize,
+    {
+        let raw =
+            serde_json::to_value(&value).map_err(|e| ConfigError::serialization("json", e))?;
+
+        Ok(Self {
+            raw,
+            value: OnceLock::from(value),
+        })
+    }
+
+    /// Returns the value, deserializing the raw data on the first call and returning the cached value afterwards
+    ///
+    /// ## Returns
+    ///
+    /// A reference to the value
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConfigError::Deserialization`]: The raw data could not be deserialized into `T`, the next call will try again
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use configura::lazy::Lazy;
+    ///
+    /// let words = Lazy::new(vec!["hello".to_string()]).unwrap();
+    /// assert_eq!(words.get().unwrap(), &vec!["hello".to_string()]);
+    /// ```
+    pub fn get(&self) -> Result<&T>
+    where
+        T: DeserializeOwned,
+    {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let value = serde_json::from_value(self.raw.clone())
+            .map_err(|e| ConfigError::deserialization("json", e))?;
+        Ok(self.value.get_or_init(|| value))
+    }
+
+    /// Returns whether the raw data has already been deserialized
+    #[must_use]
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Returns the raw data
+    #[must_use]
+    pub fn raw(&self) -> &Value {
+        &self.raw
+    }
+}
+
+impl<T> Default for Lazy<T>
+where
+    T: Default + Serialize,
+{
+    fn default() -> Self {
+        Self::new(T::default()).unwrap_or_else(|_| Self {
+            raw: Value::Null,
+            value: OnceLock::new(),
+        })
+    }
+}
+
+impl<T> PartialEq for Lazy<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl<T> Serialize for Lazy<T> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.raw.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Lazy<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self {
+            raw: Value::deserialize(deserializer)?,
+            value: OnceLock::new(),
+        })
+    }
+}
@@ -3,14 +3,20 @@
 
 pub mod errors;
 pub mod formats;
+#[cfg(feature = "json")]
+pub mod lazy;
 
 use dirs::home_dir;
 use errors::{ConfigError, Result};
+#[cfg(feature = "gzip")]
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
-    fs::{canonicalize, create_dir_all, rename, File, OpenOptions},
-    io::{BufReader, BufWriter, Read, Write},
+    collections::HashMap,
+    fs::{canonicalize, copy, create_dir_all, remove_file, rename, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Write},
     path::{Path, PathBuf},
+    sync::{Mutex, OnceLock, PoisonError},
 };
 
 pub trait Config: Serialize + DeserializeOwned + PartialEq + Default {
@@ -53,6 +59,138 @@ pub trait Config: Serialize + DeserializeOwned + PartialEq + Default {
         (None, "")
     }
 
+    /// The Unix permission bits to create the config file with, e.g. `Some(0o600)` for files that contain secrets.
+    ///
+    /// Has no effect on non-Unix platforms.
+    ///
+    /// ## Returns
+    ///
+    /// * `Option<u32>` - The mode of the file, the default umask will be used if `None` is returned.
+    #[must_use]
+    fn file_mode() -> Option<u32> {
+        None
+    }
+
+    /// Whether to read the temporary file back and compare it to the config before replacing the config file,
+    /// if they don't match the temporary file is removed and the config file is left untouched.
+    #[must_use]
+    fn verify_on_write() -> bool {
+        false
+    }
+
+    /// Whether the config file should be compressed with gzip, in which case `.gz` is appended to the extension.
+    ///
+    /// Requires the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    #[must_use]
+    fn compressed() -> bool {
+        false
+    }
+
+    /// Whether to keep the fields of the config file that are unknown to the config when saving, for example fields
+    /// added by a newer version of the program. The data in the file is merged with the config data as
+    /// [`serde_json::Value`], objects are merged recursively and the keys missing from the config are kept, which means
+    /// fields that are skipped when serializing (like an `Option` with `skip_serializing_if`) keep their old value.
+    ///
+    /// Works with any self-describing format but the keys may be reordered. Alternatively the config can hold the
+    /// unknown fields itself with a `#[serde(flatten)] extra: HashMap<String, serde_json::Value>` field.
+    ///
+    /// Requires the `json` feature.
+    #[cfg(feature = "json")]
+    #[must_use]
+    fn preserve_unknown() -> bool {
+        false
+    }
+
+    /// A cheap fingerprint of the config data, like a hash, used to track whether it changed since it was last
+    /// loaded or saved. If `Some` is returned [`Config::save`] skips serializing and writing the data when the
+    /// fingerprint matches the one from the last save, so it must change whenever the data does.
+    ///
+    /// The fingerprints are kept in memory for the duration of the program, changes made to the file by other
+    /// programs are not detected.
+    ///
+    /// ## Returns
+    ///
+    /// * `Option<u64>` - The fingerprint of the data, changes are not tracked if `None` is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use configura::{Config, formats::JsonFormat};
+    /// use serde::{Deserialize, Serialize};
+    /// use std::hash::{DefaultHasher, Hash, Hasher};
+    ///
+    /// #[derive(Debug, Default, Hash, Serialize, Deserialize, PartialEq)]
+    /// struct ConfigData {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Config for ConfigData {
+    ///     type FormatType = JsonFormat;
+    ///     type FormatContext = ();
+    ///
+    ///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+    ///         (None, "config")
+    ///     }
+    ///
+    ///     fn fingerprint(&self) -> Option<u64> {
+    ///         let mut hasher = DefaultHasher::new();
+    ///         self.hash(&mut hasher);
+    ///         Some(hasher.finish())
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    fn fingerprint(&self) -> Option<u64> {
+        None
+    }
+
+    /// Whether the config changed since it was last loaded from or saved to file, based on its
+    /// [fingerprint](Config::fingerprint). Always `true` if the config has no fingerprint or was never loaded/saved.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use configura::{Config, formats::JsonFormat};
+    /// use serde::{Deserialize, Serialize};
+    /// use std::hash::{DefaultHasher, Hash, Hasher};
+    ///
+    /// #[derive(Debug, Default, Hash, Serialize, Deserialize, PartialEq)]
+    /// struct ConfigData {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Config for ConfigData {
+    ///     type FormatType = JsonFormat;
+    ///     type FormatContext = ();
+    ///
+    ///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+    ///         (None, "config")
+    ///     }
+    ///
+    ///     fn fingerprint(&self) -> Option<u64> {
+    ///         let mut hasher = DefaultHasher::new();
+    ///         self.hash(&mut hasher);
+    ///         Some(hasher.finish())
+    ///     }
+    /// }
+    ///
+    /// let mut data = ConfigData::default();
+    /// data.save().unwrap();
+    /// assert!(!data.is_dirty());
+    ///
+    /// data.name = "John".into();
+    /// assert!(data.is_dirty());
+    /// ```
+    #[must_use]
+    fn is_dirty(&self) -> bool {
+        let (Some(fingerprint), Ok(path)) = (self.fingerprint(), self.path()) else {
+            return true;
+        };
+
+        saved_fingerprint(&path) != Some(fingerprint)
+    }
+
     /// Load the config from file.
     ///
     /// ## Example
@@ -91,13 +229,22 @@ pub trait Config: Serialize + DeserializeOwned + PartialEq + Default {
     /// - [`ConfigError::Deserialization`]: Deserialization error
     /// - [`ConfigError::Io`]: IO error
     /// - [`ConfigError::NoHomeDir`]: No home directory found
+    /// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
     fn load(&mut self) -> Result<()> {
-        let data: Self = load_config()?;
+        let (data, source): (Self, _) = load_config_with_source()?;
+        if source == ConfigSource::MainFile {
+            set_saved_fingerprint(&self.path()?, data.fingerprint());
+        }
+
         *self = data;
         Ok(())
     }
 
-    /// Save the config to file.
+    /// Save the config to file, the main and mirror files are only written if their contents changed.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the main file was written, `false` if it already had the same contents
     ///
     /// ## Errors
     ///
@@ -106,35 +253,201 @@ pub trait Config: Serialize + DeserializeOwned + PartialEq + Default {
     ///   which means the previous write failed
     /// - [`ConfigError::Io`]: IO error
     /// - [`ConfigError::NoHomeDir`]: No home directory found
+    /// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
     /// - [`ConfigError::Serialization`]: Serialization error
-    fn save(&self) -> Result<()> {
-        let path = self.path()?;
+    /// - [`ConfigError::VerificationFailed`]: The written data does not match the config,
+    ///   only if [`Config::verify_on_write`] is enabled
+    fn save(&self) -> Result<bool> {
+        save_config(self, &self.path()?, self.get_mirror_path()?)
+    }
+
+    /// Save the config to file in `dir` instead of the folder from [`Config::config_path_and_filename`], keeping the
+    /// filename and extension. The mirror file is not written.
+    ///
+    /// This is useful when the folder is only known at runtime, like from a `--config-dir` argument, and can be loaded
+    /// back with [`load_config_in`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `dir` - The folder to save the config file in, created if it does not exist
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the file was written, `false` if it already had the same contents
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use configura::{Config, load_config_in, formats::JsonFormat};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    /// struct ConfigData {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Config for ConfigData {
+    ///     type FormatType = JsonFormat;
+    ///     type FormatContext = ();
+    ///
+    ///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+    ///         (None, "config")
+    ///     }
+    /// }
+    ///
+    /// let dir = std::path::Path::new("/path/to/config/dir");
+    /// let mut data: ConfigData = load_config_in(dir).unwrap();
+    ///
+    /// data.age = 30;
+    /// data.save_in(dir).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConfigError::Deserialization`]: Deserialization error
+    /// - [`ConfigError::FailedWrite`]: Failed to write file because it already exists,
+    ///   which means the previous write failed
+    /// - [`ConfigError::Io`]: IO error
+    /// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+    /// - [`ConfigError::Serialization`]: Serialization error
+    /// - [`ConfigError::VerificationFailed`]: The written data does not match the config,
+    ///   only if [`Config::verify_on_write`] is enabled
+    fn save_in(&self, dir: &Path) -> Result<bool> {
+        save_config(self, &path_in::<Self>(dir), None)
+    }
+
+    /// Compare the config with the data currently saved to file and return the paths of the fields that differ in
+    /// alphabetical order, nested fields are separated by a dot (e.g. `window.width`). If no file exists the data is
+    /// compared to the default.
+    ///
+    /// Requires the `json` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use configura::{Config, formats::JsonFormat};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    /// struct ConfigData {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Config for ConfigData {
+    ///     type FormatType = JsonFormat;
+    ///     type FormatContext = ();
+    ///
+    ///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+    ///         (None, "config")
+    ///     }
+    /// }
+    ///
+    /// let mut data = ConfigData::default();
+    /// data.save().unwrap();
+    ///
+    /// data.age = 30;
+    /// assert_eq!(data.changed_fields().unwrap(), vec!["age"]);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConfigError::Deserialization`]: Deserialization error
+    /// - [`ConfigError::Io`]: IO error
+    /// - [`ConfigError::NoHomeDir`]: No home directory found
+    /// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+    /// - [`ConfigError::Serialization`]: Serialization error
+    #[cfg(feature = "json")]
+    fn changed_fields(&self) -> Result<Vec<String>> {
+        let saved: Self = load_config()?;
+        if &saved == self {
+            return Ok(Vec::new());
+        }
+
+        let saved =
+            serde_json::to_value(&saved).map_err(|e| ConfigError::serialization("json", e))?;
+        let current =
+            serde_json::to_value(self).map_err(|e| ConfigError::serialization("json", e))?;
+
+        let mut fields = Vec::new();
+        diff_values("", &saved, &current, &mut fields);
+        fields.sort();
+        Ok(fields)
+    }
 
-        // write main file
-        self.write_file(&path)?;
+    /// Delete the config file and mirror file (if any) and reset the config to its default values.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use configura::{Config, formats::JsonFormat};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    /// struct ConfigData {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Config for ConfigData {
+    ///     type FormatType = JsonFormat;
+    ///     type FormatContext = ();
+    ///
+    ///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+    ///         (None, "config")
+    ///     }
+    /// }
+    ///
+    /// let mut data = ConfigData { name: "John".into() };
+    /// data.save().unwrap();
+    ///
+    /// data.reset().unwrap();
+    /// assert_eq!(data, ConfigData::default());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConfigError::Io`]: IO error
+    /// - [`ConfigError::NoHomeDir`]: No home directory found
+    /// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+    fn reset(&mut self) -> Result<()> {
+        let path = self.path()?;
+        remove_file_optional(&path)?;
+        set_saved_fingerprint(&path, None);
 
-        // write mirror/backup file
         if let Some(mirror_path) = self.get_mirror_path()? {
-            self.write_file(&mirror_path)?;
+            remove_file_optional(&mirror_path)?;
         }
 
+        *self = Self::default();
         Ok(())
     }
 
     /// Write the config to file.
     ///
+    /// The data is written to a temporary file next to `path` which is then renamed over it, if the rename fails because
+    /// both files are on different filesystems the temporary file is copied over `path` instead, in which case the write
+    /// is no longer atomic and an interruption can leave `path` partially written.
+    ///
     /// ## Arguments
     ///
     /// * `path` - The path to the file.
     ///
+    /// ## Returns
+    ///
+    /// `true` if the file was written, `false` if it already had the same contents
+    ///
     /// ## Errors
     ///
     /// - [`ConfigError::Deserialization`]: Deserialization error
     /// - [`ConfigError::FailedWrite`]: Failed to write file because it already exists,
     ///   which means the previous write failed
     /// - [`ConfigError::Io`]: IO error
+    /// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
     /// - [`ConfigError::Serialization`]: Serialization error
-    fn write_file(&self, path: &PathBuf) -> Result<()> {
+    /// - [`ConfigError::VerificationFailed`]: The written data does not match the config,
+    ///   only if [`Config::verify_on_write`] is enabled
+    fn write_file(&self, path: &PathBuf) -> Result<bool> {
         let original_filename = path.file_name().unwrap_or_default();
         let mut temp_filename = original_filename.to_os_string();
 
@@ -142,7 +455,7 @@ pub trait Config: Serialize + DeserializeOwned + PartialEq + Default {
         let temp_path = path.with_file_name(temp_filename);
 
         if let Some(parent) = temp_path.parent() {
-            create_dir_all(parent)?;
+            create_dir_all(parent).map_err(|e| ConfigError::io(parent, &e))?;
         }
 
         if temp_path.is_file() {
@@ -155,25 +468,80 @@ pub trait Config: Serialize + DeserializeOwned + PartialEq + Default {
         }
 
         let context = self.format_context();
+        let existing = match read_from_file(path, is_compressed::<Self>()) {
+            Ok(data) => Some(data),
+            Err(
+                ConfigError::Io(..) | ConfigError::NotFound(_) | ConfigError::PermissionDenied(_),
+            ) => None,
+            Err(e) => return Err(e),
+        };
+
+        #[cfg(feature = "json")]
+        let data_str = if Self::preserve_unknown() {
+            serialize_preserving_unknown(self, existing.as_deref(), &context)?
+        } else {
+            Self::FormatType::to_string(self, false, Some(&context))?
+        };
+
+        #[cfg(not(feature = "json"))]
         let data_str = Self::FormatType::to_string(self, false, Some(&context))?;
 
-        match read_from_file(path) {
-            Ok(data) if data == data_str => return Ok(()),
-            Ok(_) | Err(ConfigError::Io(_)) => (),
-            Err(e) => return Err(e),
+        if existing.is_some_and(|data| data == data_str) {
+            return Ok(false);
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        #[cfg(unix)]
+        if let Some(mode) = Self::file_mode() {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(mode);
         }
 
-        let temp_file = OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&temp_path)?;
+        let temp_file = options
+            .open(&temp_path)
+            .map_err(|e| ConfigError::io(&temp_path, &e))?;
         let mut writer = BufWriter::new(temp_file);
 
-        writer.write_all(data_str.as_bytes())?;
+        #[cfg(feature = "gzip")]
+        if Self::compressed() {
+            let mut encoder = GzEncoder::new(writer, Compression::default());
+            encoder
+                .write_all(data_str.as_bytes())
+                .map_err(|e| ConfigError::io(&temp_path, &e))?;
+            writer = encoder
+                .finish()
+                .map_err(|e| ConfigError::io(&temp_path, &e))?;
+        } else {
+            writer
+                .write_all(data_str.as_bytes())
+                .map_err(|e| ConfigError::io(&temp_path, &e))?;
+        }
+
+        #[cfg(not(feature = "gzip"))]
+        writer
+            .write_all(data_str.as_bytes())
+            .map_err(|e| ConfigError::io(&temp_path, &e))?;
 
         drop(writer);
-        rename(temp_path, path)?;
-        Ok(())
+
+        if Self::verify_on_write() {
+            let file = File::open(&temp_path).map_err(|e| ConfigError::io(&temp_path, &e))?;
+            let reader = BufReader::new(open_reader(file, is_compressed::<Self>()));
+            let verified = match Self::FormatType::from_reader::<_, Self>(reader, Some(&context)) {
+                Ok(data) => &data == self,
+                Err(_) => false,
+            };
+
+            if !verified {
+                remove_file(&temp_path).map_err(|e| ConfigError::io(&temp_path, &e))?;
+                return Err(ConfigError::VerificationFailed(path.display().to_string()));
+            }
+        }
+
+        commit_temp_file(&temp_path, path, |from, to| rename(from, to))?;
+        Ok(true)
     }
 
     /// Convert the config data to a String based on the format.
@@ -301,59 +669,372 @@ pub trait Format<C> {
 /// - [`ConfigError::Deserialization`]: Deserialization error
 /// - [`ConfigError::Io`]: IO error
 /// - [`ConfigError::NoHomeDir`]: No home directory found
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
 pub fn load_config<T>() -> Result<T>
 where
     T: Config,
 {
-    let main_path = final_path::<T>()?;
-
-    let file_to_load = match try_open_optional(&main_path)? {
-        Some(file) => file, // main file exists, use it
-        None => {
-            // main file does not exist, try mirror
-            if let Some(mirror_path) = final_mirror_path::<T>()? {
-                match try_open_optional(&mirror_path)? {
-                    Some(file) => file,
-                    None => return Ok(T::default()), // both main and mirror are missing, return default
-                }
-            } else {
-                return Ok(T::default()); // no mirror provided and main file does not exist
-            }
-        }
-    };
-
-    let context = T::default().format_context();
-    let data: T = T::FormatType::from_reader(BufReader::new(file_to_load), Some(&context))?;
-    Ok(data)
+    load_config_with_source().map(|(data, _)| data)
 }
 
-/// Read the contents of a file into a String.
+/// Load the config data from file in `dir` instead of the folder from [`Config::config_path_and_filename`], keeping the
+/// filename and extension. The mirror file is not used, if the file does not exist the default data is returned.
+///
+/// This is useful when the folder is only known at runtime, like from a `--config-dir` argument, and can be saved back
+/// with [`Config::save_in`].
 ///
 /// ## Arguments
 ///
-/// * `path` - The path to the file.
+/// * `dir` - The folder to load the config file from
 ///
-/// ## Returns
+/// ## Example
 ///
-/// * `String` - The contents of the file.
+/// ```rust,no_run
+/// use configura::{Config, load_config_in, formats::JsonFormat};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+/// struct ConfigData {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// impl Config for ConfigData {
+///     type FormatType = JsonFormat;
+///     type FormatContext = ();
+///
+///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+///         (None, "config")
+///     }
+/// }
+///
+/// let data: ConfigData = load_config_in(std::path::Path::new("/path/to/config/dir")).unwrap();
+/// ```
 ///
 /// ## Errors
 ///
+/// - [`ConfigError::Deserialization`]: Deserialization error
 /// - [`ConfigError::Io`]: IO error
-pub(crate) fn read_from_file<P>(path: P) -> Result<String>
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+pub fn load_config_in<T>(dir: &Path) -> Result<T>
 where
-    P: AsRef<Path>,
+    T: Config,
 {
-    let file = File::open(&path)?;
-    let mut reader = BufReader::new(file);
-    let mut buffer = String::new();
+    match try_open_optional(&path_in::<T>(dir))? {
+        Some(file) => read_config(file),
+        None => Ok(T::default()),
+    }
+}
 
-    reader.read_to_string(&mut buffer)?;
+/// Where the config data was loaded from by [`load_config_with_source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfigSource {
+    /// The data was loaded from the main config file
+    MainFile,
+
+    /// The main config file does not exist so the data was loaded from the mirror file
+    MirrorFile,
+
+    /// Neither file exists so the default data was used
+    Default,
+}
+
+/// Load the config data from file, along with where it was loaded from.
+///
+/// This is useful to tell a fresh install, where [`ConfigSource::Default`] is returned, from an existing config.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use configura::{Config, ConfigSource, load_config_with_source, formats::JsonFormat};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+/// struct ConfigData {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// impl Config for ConfigData {
+///     type FormatType = JsonFormat;
+///     type FormatContext = ();
+///
+///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+///         (None, "config")
+///     }
+/// }
+///
+/// let (data, source): (ConfigData, _) = load_config_with_source().unwrap();
+/// if source == ConfigSource::Default {
+///     // first run
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// - [`ConfigError::Deserialization`]: Deserialization error
+/// - [`ConfigError::Io`]: IO error
+/// - [`ConfigError::NoHomeDir`]: No home directory found
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+pub fn load_config_with_source<T>() -> Result<(T, ConfigSource)>
+where
+    T: Config,
+{
+    let Some((file_to_load, source)) = open_config_file::<T>()? else {
+        return Ok((T::default(), ConfigSource::Default));
+    };
+
+    Ok((read_config(file_to_load)?, source))
+}
+
+/// Load the config data from file, or create it with `f` if neither the main file nor the mirror file exist.
+///
+/// This is useful when the default depends on runtime information, like a detected path. Nothing is written to file
+/// until the config is saved.
+///
+/// ## Arguments
+///
+/// * `f` - The function that creates the config data, only called if there's no file to load
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use configura::{Config, load_config_or_else, formats::JsonFormat};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+/// struct ConfigData {
+///     machine: String,
+/// }
+///
+/// impl Config for ConfigData {
+///     type FormatType = JsonFormat;
+///     type FormatContext = ();
+///
+///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+///         (None, "config")
+///     }
+/// }
+///
+/// let data: ConfigData = load_config_or_else(|| ConfigData {
+///     machine: std::env::var("HOSTNAME").unwrap_or_default(),
+/// })
+/// .unwrap();
+/// ```
+///
+/// ## Errors
+///
+/// - [`ConfigError::Deserialization`]: Deserialization error
+/// - [`ConfigError::Io`]: IO error
+/// - [`ConfigError::NoHomeDir`]: No home directory found
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+pub fn load_config_or_else<T, F>(f: F) -> Result<T>
+where
+    T: Config,
+    F: FnOnce() -> T,
+{
+    match open_config_file::<T>()? {
+        Some((file_to_load, _)) => read_config(file_to_load),
+        None => Ok(f()),
+    }
+}
+
+/// Deserialize the config data from an opened config file.
+///
+/// ## Arguments
+///
+/// * `file` - The main or mirror config file.
+///
+/// ## Errors
+///
+/// - [`ConfigError::Deserialization`]: Deserialization error
+fn read_config<T>(file: File) -> Result<T>
+where
+    T: Config,
+{
+    let context = T::default().format_context();
+    let reader = open_reader(file, is_compressed::<T>());
+    T::FormatType::from_reader(BufReader::new(reader), Some(&context))
+}
+
+/// Load the config data from file and merge overlay files over it, like a `config.local.toml` next to `config.toml`.
+///
+/// The overlays are looked for in the same folder as the config file and use the same extension, the ones that don't
+/// exist are skipped. Each overlay only needs the fields it overrides, objects are merged recursively while any other
+/// value (including arrays) replaces the value underneath it, and later overlays override earlier ones.
+///
+/// Requires the `json` feature since the files are merged as [`serde_json::Value`].
+///
+/// ## Arguments
+///
+/// * `overlay_filenames` - The filenames of the overlays without the extension, in the order they're applied
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use configura::{Config, load_config_overlaid, formats::JsonFormat};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+/// struct ConfigData {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// impl Config for ConfigData {
+///     type FormatType = JsonFormat;
+///     type FormatContext = ();
+///
+///     fn config_path_and_filename(_: &std::path::Path) -> (Option<std::path::PathBuf>, &str) {
+///         (None, "config")
+///     }
+/// }
+///
+/// // loads `config.json` then `config.local.json` over it
+/// let data: ConfigData = load_config_overlaid(&["config.local"]).unwrap();
+/// ```
+///
+/// ## Errors
+///
+/// - [`ConfigError::Deserialization`]: Deserialization error
+/// - [`ConfigError::Io`]: IO error
+/// - [`ConfigError::NoHomeDir`]: No home directory found
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+/// - [`ConfigError::Serialization`]: Serialization error
+#[cfg(feature = "json")]
+pub fn load_config_overlaid<T>(overlay_filenames: &[&str]) -> Result<T>
+where
+    T: Config,
+{
+    let context = T::default().format_context();
+    let compressed = is_compressed::<T>();
+
+    let mut merged = match open_config_file::<T>()? {
+        Some((file, _)) => T::FormatType::from_reader(
+            BufReader::new(open_reader(file, compressed)),
+            Some(&context),
+        )?,
+        None => {
+            serde_json::to_value(T::default()).map_err(|e| ConfigError::serialization("json", e))?
+        }
+    };
+
+    let main_path = final_path::<T>()?;
+    let folder = main_path.parent().unwrap_or(Path::new(""));
+
+    for filename in overlay_filenames {
+        let path = folder.join(format!("{filename}.{}", file_extension::<T>()));
+        if let Some(file) = try_open_optional(&path)? {
+            let overlay: serde_json::Value = T::FormatType::from_reader(
+                BufReader::new(open_reader(file, compressed)),
+                Some(&context),
+            )?;
+            merge_values(&mut merged, overlay);
+        }
+    }
+
+    serde_json::from_value(merged).map_err(|e| ConfigError::deserialization("json", e))
+}
+
+/// Open the main config file, or the mirror file if the main one does not exist.
+///
+/// ## Returns
+///
+/// * `Option<(File, ConfigSource)>` - The opened file and which one it is, `None` if neither exists.
+///
+/// ## Errors
+///
+/// - [`ConfigError::Io`]: IO error
+/// - [`ConfigError::NoHomeDir`]: No home directory found
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+pub(crate) fn open_config_file<T>() -> Result<Option<(File, ConfigSource)>>
+where
+    T: Config,
+{
+    let main_path = final_path::<T>()?;
+
+    if let Some(file) = try_open_optional(&main_path)? {
+        return Ok(Some((file, ConfigSource::MainFile))); // main file exists, use it
+    }
+
+    // main file does not exist, try mirror
+    if let Some(mirror_path) = final_mirror_path::<T>()? {
+        if let Some(file) = try_open_optional(&mirror_path)? {
+            return Ok(Some((file, ConfigSource::MirrorFile)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Read the contents of a file into a String.
+///
+/// ## Arguments
+///
+/// * `path` - The path to the file.
+/// * `compressed` - Whether the file is compressed with gzip.
+///
+/// ## Returns
+///
+/// * `String` - The contents of the file.
+///
+/// ## Errors
+///
+/// - [`ConfigError::Io`]: IO error
+/// - [`ConfigError::NotFound`]: The file does not exist
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+pub(crate) fn read_from_file<P>(path: P, compressed: bool) -> Result<String>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(&path).map_err(|e| ConfigError::io(path.as_ref(), &e))?;
+    let mut reader = BufReader::new(open_reader(file, compressed));
+    let mut buffer = String::new();
+
+    reader
+        .read_to_string(&mut buffer)
+        .map_err(|e| ConfigError::io(path.as_ref(), &e))?;
 
     drop(reader);
     Ok(buffer)
 }
 
+/// Wrap a file in a gzip decoder if it's compressed.
+#[allow(unused_variables)]
+pub(crate) fn open_reader(file: File, compressed: bool) -> Box<dyn Read> {
+    #[cfg(feature = "gzip")]
+    if compressed {
+        return Box::new(GzDecoder::new(file));
+    }
+
+    Box::new(file)
+}
+
+/// Whether the config file is compressed with gzip.
+#[cfg_attr(not(feature = "gzip"), allow(clippy::extra_unused_type_parameters))]
+pub(crate) fn is_compressed<T>() -> bool
+where
+    T: Config,
+{
+    #[cfg(feature = "gzip")]
+    return T::compressed();
+
+    #[cfg(not(feature = "gzip"))]
+    false
+}
+
+/// Get the extension of the config file, including `.gz` if it's compressed.
+pub(crate) fn file_extension<T>() -> String
+where
+    T: Config,
+{
+    if is_compressed::<T>() {
+        format!("{}.gz", T::FormatType::EXTENSION)
+    } else {
+        T::FormatType::EXTENSION.to_string()
+    }
+}
+
 /// Get the path to the config file.
 ///
 /// ## Returns
@@ -371,7 +1052,26 @@ where
     let (path, filename) = T::config_path_and_filename(&home);
     Ok(path
         .unwrap_or(home.clone())
-        .join(format!("{filename}.{}", T::FormatType::EXTENSION)))
+        .join(format!("{filename}.{}", file_extension::<T>())))
+}
+
+/// Get the path to the config file in `dir`, using the filename from [`Config::config_path_and_filename`].
+///
+/// ## Arguments
+///
+/// * `dir` - The folder of the config file.
+///
+/// ## Returns
+///
+/// * `PathBuf` - The full path to the config file.
+pub(crate) fn path_in<T>(dir: &Path) -> PathBuf
+where
+    T: Config,
+{
+    // only the filename is used so the home directory doesn't need to exist
+    let home = home_dir().unwrap_or_default();
+    let (_, filename) = T::config_path_and_filename(&home);
+    dir.join(format!("{filename}.{}", file_extension::<T>()))
 }
 
 /// Get the path to the mirror file.
@@ -395,29 +1095,297 @@ where
             return Ok(None);
         }
         return Ok(Some(
-            path.join(format!("{filename}.{}", T::FormatType::EXTENSION)),
+            path.join(format!("{filename}.{}", file_extension::<T>())),
         ));
     }
 
     Ok(None)
 }
 
+/// Save the config to the main file and the mirror file, the files are only written if their contents changed.
+///
+/// ## Arguments
+///
+/// * `config` - The config data.
+/// * `path` - The path to the main file.
+/// * `mirror_path` - The path to the mirror file, if any.
+///
+/// ## Returns
+///
+/// `true` if the main file was written, `false` if it already had the same contents
+///
+/// ## Errors
+///
+/// - [`ConfigError::Deserialization`]: Deserialization error
+/// - [`ConfigError::FailedWrite`]: Failed to write file because it already exists,
+///   which means the previous write failed
+/// - [`ConfigError::Io`]: IO error
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+/// - [`ConfigError::Serialization`]: Serialization error
+/// - [`ConfigError::VerificationFailed`]: The written data does not match the config,
+///   only if [`Config::verify_on_write`] is enabled
+fn save_config<T>(config: &T, path: &PathBuf, mirror_path: Option<PathBuf>) -> Result<bool>
+where
+    T: Config,
+{
+    let fingerprint = config.fingerprint();
+    if fingerprint.is_some() && saved_fingerprint(path) == fingerprint {
+        return Ok(false); // nothing changed since the last save
+    }
+
+    // write main file
+    let written = config.write_file(path)?;
+
+    // write mirror/backup file
+    if let Some(mirror_path) = mirror_path {
+        config.write_file(&mirror_path)?;
+    }
+
+    set_saved_fingerprint(path, fingerprint);
+    Ok(written)
+}
+
+/// The OS error code for a rename across filesystems.
+#[cfg(unix)]
+const CROSS_DEVICE_ERROR: i32 = 18; // EXDEV
+
+/// The OS error code for a rename across filesystems.
+#[cfg(windows)]
+const CROSS_DEVICE_ERROR: i32 = 17; // ERROR_NOT_SAME_DEVICE
+
+/// Check if an IO error was caused by renaming a file across filesystems.
+fn is_cross_device_error(error: &io::Error) -> bool {
+    #[cfg(any(unix, windows))]
+    {
+        error.raw_os_error() == Some(CROSS_DEVICE_ERROR)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = error;
+        false
+    }
+}
+
+/// Move the temporary file to its final path using `rename_fn`, falling back to copying and removing it
+/// if the files are on different filesystems.
+///
+/// ## Arguments
+///
+/// * `temp_path` - The path to the temporary file.
+/// * `path` - The final path of the file.
+/// * `rename_fn` - The function used to rename the file.
+///
+/// ## Errors
+///
+/// - [`ConfigError::Io`]: IO error
+/// - [`ConfigError::PermissionDenied`]: Permission denied to access a file or directory
+pub(crate) fn commit_temp_file<F>(temp_path: &Path, path: &Path, rename_fn: F) -> Result<()>
+where
+    F: FnOnce(&Path, &Path) -> io::Result<()>,
+{
+    match rename_fn(temp_path, path) {
+        Ok(()) => Ok(()),
+        Err(e) if is_cross_device_error(&e) => {
+            copy(temp_path, path).map_err(|e| ConfigError::io(path, &e))?;
+            remove_file(temp_path).map_err(|e| ConfigError::io(temp_path, &e))?;
+            Ok(())
+        }
+        Err(e) => Err(ConfigError::io(path, &e)),
+    }
+}
+
 /// Open a file, or return `None` if the file does not exist.
 pub(crate) fn try_open_optional(path: &Path) -> Result<Option<File>> {
     match File::open(path) {
         Ok(file) => Ok(Some(file)),
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
-        Err(e) => Err(e.into()),
+        Err(e) => Err(ConfigError::io(path, &e)),
+    }
+}
+
+/// Compare two values and push the paths of the fields that differ into `fields`.
+///
+/// ## Arguments
+///
+/// * `path` - The path of the values being compared, empty for the root.
+/// * `old` - The old value.
+/// * `new` - The new value.
+/// * `fields` - The list of differing field paths.
+#[cfg(feature = "json")]
+pub(crate) fn diff_values(
+    path: &str,
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    fields: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                match new_map.get(key) {
+                    Some(new_value) => diff_values(&join(key), old_value, new_value, fields),
+                    None => fields.push(join(key)),
+                }
+            }
+
+            for key in new_map.keys() {
+                if !old_map.contains_key(key) {
+                    fields.push(join(key));
+                }
+            }
+        }
+        _ if old != new => fields.push(path.to_string()),
+        _ => (),
+    }
+}
+
+/// Serialize the config data keeping the fields of `existing` that are unknown to the config, falls back to
+/// serializing only the config data if `existing` is `None` or can't be parsed.
+///
+/// ## Arguments
+///
+/// * `data` - The config data.
+/// * `existing` - The current contents of the config file.
+/// * `context` - The context for the format.
+///
+/// ## Errors
+///
+/// - [`ConfigError::Serialization`]: Serialization error
+#[cfg(feature = "json")]
+pub(crate) fn serialize_preserving_unknown<T>(
+    data: &T,
+    existing: Option<&str>,
+    context: &T::FormatContext,
+) -> Result<String>
+where
+    T: Config,
+{
+    let existing = existing.and_then(|existing| {
+        T::FormatType::from_reader::<_, serde_json::Value>(existing.as_bytes(), Some(context)).ok()
+    });
+
+    let Some(existing) = existing else {
+        return T::FormatType::to_string(data, false, Some(context));
+    };
+
+    let mut value =
+        serde_json::to_value(data).map_err(|e| ConfigError::serialization("json", e))?;
+    keep_unknown_values(&mut value, existing);
+    T::FormatType::to_string(&value, false, Some(context))
+}
+
+/// Add the keys of `old` that are missing from `new`, objects are merged recursively and any other value is left as
+/// it is.
+///
+/// ## Arguments
+///
+/// * `new` - The value to add the keys to.
+/// * `old` - The value to take the missing keys from.
+#[cfg(feature = "json")]
+pub(crate) fn keep_unknown_values(new: &mut serde_json::Value, old: serde_json::Value) {
+    use serde_json::Value;
+
+    if let (Value::Object(new_map), Value::Object(old_map)) = (new, old) {
+        for (key, old_value) in old_map {
+            match new_map.get_mut(&key) {
+                Some(new_value) => keep_unknown_values(new_value, old_value),
+                None => {
+                    new_map.insert(key, old_value);
+                }
+            }
+        }
+    }
+}
+
+/// Merge a value over another, objects are merged recursively and any other value replaces the base.
+///
+/// ## Arguments
+///
+/// * `base` - The value to merge into.
+/// * `overlay` - The value to merge over `base`.
+#[cfg(feature = "json")]
+pub(crate) fn merge_values(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    use serde_json::Value;
+
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            for (key, overlay_value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(base_value) => merge_values(base_value, overlay_value),
+                    None => {
+                        base_map.insert(key, overlay_value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// The fingerprints of the configs as they were last loaded or saved, by the path of their file.
+fn saved_fingerprints() -> &'static Mutex<HashMap<PathBuf, u64>> {
+    static FINGERPRINTS: OnceLock<Mutex<HashMap<PathBuf, u64>>> = OnceLock::new();
+    FINGERPRINTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Get the fingerprint of the config file at `path` from when it was last loaded or saved.
+pub(crate) fn saved_fingerprint(path: &Path) -> Option<u64> {
+    saved_fingerprints()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(path)
+        .copied()
+}
+
+/// Set the fingerprint of the config file at `path`, or forget it if `fingerprint` is `None`.
+pub(crate) fn set_saved_fingerprint(path: &Path, fingerprint: Option<u64>) {
+    let mut fingerprints = saved_fingerprints()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    match fingerprint {
+        Some(fingerprint) => fingerprints.insert(path.to_path_buf(), fingerprint),
+        None => fingerprints.remove(path),
+    };
+}
+
+/// Remove a file, ignoring the error if the file does not exist.
+pub(crate) fn remove_file_optional(path: &Path) -> Result<()> {
+    match remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(ConfigError::io(path, &e)),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{load_config, Config, Result};
+    #[cfg(feature = "json")]
+    use super::load_config_overlaid;
+    use super::{
+        commit_temp_file, load_config, load_config_in, load_config_or_else,
+        load_config_with_source, read_from_file, Config, ConfigError, ConfigSource, Result,
+    };
     use serde::{Deserialize, Serialize};
-    use std::{fmt::Debug, fs::remove_file, path::PathBuf};
+    use std::{
+        fmt::Debug,
+        fs::{read_dir, read_to_string, remove_file, write},
+        path::PathBuf,
+    };
     use tempfile::tempdir;
 
+    type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
     const TEST_NAME: &str = "Alice";
     const TEST_AGE: u8 = 30;
     const TEST_FILENAME: &str = "test_config";
@@ -427,7 +1395,7 @@ mod tests {
         ($format_name:ident,$format_name_mirror:ident, $format_type:path, $feature:literal) => {
             #[test]
             #[cfg(feature = $feature)]
-            fn $format_name() -> Result<()> {
+            fn $format_name() -> TestResult {
                 #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
                 struct TestConfig {
                     name: String,
@@ -462,7 +1430,7 @@ mod tests {
 
             #[test]
             #[cfg(feature = $feature)]
-            fn $format_name_mirror() -> Result<()> {
+            fn $format_name_mirror() -> TestResult {
                 #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
                 struct TestConfig {
                     name: String,
@@ -520,7 +1488,935 @@ mod tests {
         "yaml"
     );
 
-    fn run_test<T>(original: &T) -> Result<()>
+    #[test]
+    #[cfg(all(unix, feature = "json"))]
+    fn test_config_file_mode() -> TestResult {
+        use std::os::unix::fs::PermissionsExt;
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn file_mode() -> Option<u32> {
+                Some(0o600)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let config = TestConfig {
+                name: TEST_NAME.to_string(),
+            };
+            config.save()?;
+
+            let mode = std::fs::metadata(config.path()?)?.permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_config_io_error_path() -> TestResult {
+        let temp_dir = tempdir()?;
+        let missing = temp_dir.path().join("missing.json");
+
+        assert_eq!(
+            read_from_file(&missing, false),
+            Err(ConfigError::NotFound(missing.clone()))
+        );
+
+        // opening a directory works but reading it doesn't
+        let err =
+            read_from_file(temp_dir.path(), false).expect_err("Reading a directory should fail");
+        let ConfigError::Io(path, cause) = &err else {
+            panic!("Expected an IO error, got {err:?}");
+        };
+        assert_eq!(path, temp_dir.path());
+        assert_eq!(
+            err.to_string(),
+            format!("failed to access {}: {cause}", temp_dir.path().display())
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(any(unix, windows))]
+    fn test_commit_temp_file_cross_device() -> TestResult {
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().join("config.json.tmp");
+        let path = temp_dir.path().join("config.json");
+
+        write(&temp_path, "new")?;
+        write(&path, "old")?;
+
+        commit_temp_file(&temp_path, &path, |_, _| {
+            Err(std::io::Error::from_raw_os_error(super::CROSS_DEVICE_ERROR))
+        })?;
+
+        assert_eq!(read_to_string(&path)?, "new");
+        assert!(!temp_path.exists());
+
+        write(&temp_path, "newer")?;
+        let result = commit_temp_file(&temp_path, &path, |_, _| {
+            Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied))
+        });
+
+        assert_eq!(result, Err(ConfigError::PermissionDenied(path.clone())));
+        assert_eq!(read_to_string(&path)?, "new");
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_error_io() {
+        use std::io::{Error, ErrorKind};
+
+        let path = PathBuf::from("config.json");
+        assert_eq!(
+            ConfigError::io(&path, &Error::from(ErrorKind::PermissionDenied)),
+            ConfigError::PermissionDenied(path.clone())
+        );
+        assert_eq!(
+            ConfigError::io(&path, &Error::from(ErrorKind::NotFound)),
+            ConfigError::NotFound(path.clone())
+        );
+
+        let other = Error::from(ErrorKind::InvalidData);
+        assert_eq!(
+            ConfigError::io(&path, &other),
+            ConfigError::Io(path.clone(), other.to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "json"))]
+    fn test_config_permission_denied() -> TestResult {
+        use std::{
+            fs::{set_permissions, File, Permissions},
+            os::unix::fs::PermissionsExt,
+        };
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let mut config = TestConfig {
+                name: TEST_NAME.to_string(),
+            };
+            config.save()?;
+
+            let path = config.path()?;
+            assert_eq!(
+                read_from_file(path.with_extension("missing"), false),
+                Err(ConfigError::NotFound(path.with_extension("missing")))
+            );
+
+            // permissions are not enforced when running with elevated privileges, the mapping itself is checked in
+            // `test_config_error_io`
+            set_permissions(&path, Permissions::from_mode(0o000))?;
+            if File::open(&path).is_err() {
+                assert_eq!(
+                    config.load(),
+                    Err(ConfigError::PermissionDenied(path.clone()))
+                );
+                assert_eq!(
+                    read_from_file(&path, false),
+                    Err(ConfigError::PermissionDenied(path.clone()))
+                );
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_dirty_tracking() -> TestResult {
+        use super::{formats::JsonFormat, Format};
+        use std::{
+            hash::{DefaultHasher, Hash, Hasher},
+            io::Read,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        static SERIALIZATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        struct CountingFormat;
+
+        impl Format<()> for CountingFormat {
+            const EXTENSION: &'static str = "json";
+
+            type FormatContext = ();
+
+            fn to_string<T>(data: &T, pretty: bool, context: Option<&()>) -> Result<String>
+            where
+                T: Serialize,
+            {
+                SERIALIZATIONS.fetch_add(1, Ordering::SeqCst);
+                JsonFormat::to_string(data, pretty, context)
+            }
+
+            fn from_reader<R, T>(reader: R, context: Option<&()>) -> Result<T>
+            where
+                R: Read,
+                T: serde::de::DeserializeOwned,
+            {
+                JsonFormat::from_reader(reader, context)
+            }
+        }
+
+        #[derive(Debug, Default, Hash, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = CountingFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn fingerprint(&self) -> Option<u64> {
+                let mut hasher = DefaultHasher::new();
+                self.hash(&mut hasher);
+                Some(hasher.finish())
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let mut config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+            };
+            assert!(config.is_dirty());
+
+            config.save()?;
+            assert!(!config.is_dirty());
+            assert_eq!(SERIALIZATIONS.load(Ordering::SeqCst), 1);
+
+            config.save()?;
+            assert_eq!(SERIALIZATIONS.load(Ordering::SeqCst), 1);
+
+            config.age += 1;
+            assert!(config.is_dirty());
+            config.save()?;
+            assert!(!config.is_dirty());
+            assert_eq!(SERIALIZATIONS.load(Ordering::SeqCst), 2);
+
+            let mut loaded = TestConfig::default();
+            loaded.load()?;
+            assert!(!loaded.is_dirty());
+            assert_eq!(loaded, config);
+
+            config.reset()?;
+            assert!(config.is_dirty());
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_preserve_unknown() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct Window {
+            width: u32,
+        }
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            window: Window,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn preserve_unknown() -> bool {
+                true
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let mut config = TestConfig::default();
+            let path = config.path()?;
+            write(
+                &path,
+                r#"{"name":"Bob","theme":"dark","window":{"width":800,"height":600}}"#,
+            )?;
+
+            config.load()?;
+            assert_eq!(config.name, "Bob");
+
+            config.name = TEST_NAME.to_string();
+            config.window.width = 1024;
+            config.save()?;
+
+            let saved: serde_json::Value = serde_json::from_str(&read_to_string(&path)?)
+                .map_err(|e| ConfigError::deserialization("json", e))?;
+            assert_eq!(
+                saved,
+                serde_json::json!({
+                    "name": TEST_NAME,
+                    "theme": "dark",
+                    "window": {"width": 1024, "height": 600},
+                })
+            );
+
+            let mut loaded = TestConfig::default();
+            loaded.load()?;
+            assert_eq!(loaded, config);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_save_returns_written() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn mirror_path_and_filename(home: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (Some(home.to_path_buf()), TEST_FILENAME_MIRROR)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let mut config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+            };
+            assert!(config.save()?);
+            assert!(!config.save()?);
+
+            config.age += 1;
+            assert!(config.save()?);
+            assert!(!config.save()?);
+
+            let mirror_path = config.get_mirror_path()?.expect("mirror path not set");
+            assert_eq!(
+                read_to_string(mirror_path)?,
+                read_to_string(config.path()?)?
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_in_dir() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(home: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (Some(home.join("configs")), TEST_FILENAME)
+            }
+
+            fn mirror_path_and_filename(home: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (Some(home.to_path_buf()), TEST_FILENAME_MIRROR)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        let dir = temp_dir.path().join("custom").join("dir");
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let loaded: TestConfig = load_config_in(&dir)?;
+            assert_eq!(loaded, TestConfig::default());
+
+            let config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+            };
+            assert!(config.save_in(&dir)?);
+            assert!(!config.save_in(&dir)?);
+            assert!(dir.join(format!("{TEST_FILENAME}.json")).is_file());
+
+            // neither the default folder nor the mirror file are touched
+            assert!(!config.path()?.exists());
+            assert!(!config
+                .get_mirror_path()?
+                .expect("mirror path not set")
+                .exists());
+
+            let loaded: TestConfig = load_config_in(&dir)?;
+            assert_eq!(loaded, config);
+            assert_eq!(load_config::<TestConfig>()?, TestConfig::default());
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_reset() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn mirror_path_and_filename(home: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (Some(home.to_path_buf()), TEST_FILENAME_MIRROR)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let mut config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+            };
+            config.save()?;
+
+            let path = config.path()?;
+            let mirror_path = config.get_mirror_path()?.expect("mirror path not set");
+            assert!(path.is_file());
+            assert!(mirror_path.is_file());
+
+            config.reset()?;
+            assert!(!path.exists());
+            assert!(!mirror_path.exists());
+            assert_eq!(config, TestConfig::default());
+
+            // resetting again is fine even though the files are gone
+            config.reset()?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_load_config_with_source() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn mirror_path_and_filename(home: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (Some(home.to_path_buf()), TEST_FILENAME_MIRROR)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let (loaded, source): (TestConfig, _) = load_config_with_source()?;
+            assert_eq!(loaded, TestConfig::default());
+            assert_eq!(source, ConfigSource::Default);
+
+            let config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+            };
+            config.save()?;
+
+            let (loaded, source): (TestConfig, _) = load_config_with_source()?;
+            assert_eq!(loaded, config);
+            assert_eq!(source, ConfigSource::MainFile);
+
+            remove_file(config.path()?)?;
+
+            let (loaded, source): (TestConfig, _) = load_config_with_source()?;
+            assert_eq!(loaded, config);
+            assert_eq!(source, ConfigSource::MirrorFile);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_load_config_or_else() -> TestResult {
+        use std::cell::Cell;
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let calls = Cell::new(0);
+            let create = || {
+                calls.set(calls.get() + 1);
+                TestConfig {
+                    name: TEST_NAME.to_string(),
+                    age: TEST_AGE,
+                }
+            };
+
+            let config: TestConfig = load_config_or_else(create)?;
+            assert_eq!(calls.get(), 1);
+            assert_eq!(config.name, TEST_NAME);
+            assert!(!config.path()?.exists());
+
+            let saved = TestConfig {
+                name: "Bob".to_string(),
+                age: 40,
+            };
+            saved.save()?;
+
+            let config: TestConfig = load_config_or_else(create)?;
+            assert_eq!(calls.get(), 1);
+            assert_eq!(config, saved);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_changed_fields() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct Window {
+            width: u32,
+            height: u32,
+        }
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+            window: Window,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let mut config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+                window: Window {
+                    width: 800,
+                    height: 600,
+                },
+            };
+            assert_eq!(
+                config.changed_fields()?,
+                vec!["age", "name", "window.height", "window.width"]
+            );
+
+            config.save()?;
+            assert!(config.changed_fields()?.is_empty());
+
+            config.age += 1;
+            config.window.height = 720;
+            assert_eq!(config.changed_fields()?, vec!["age", "window.height"]);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(all(feature = "gzip", feature = "json"))]
+    fn test_config_compressed() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn compressed() -> bool {
+                true
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+            };
+            config.save()?;
+
+            let path = config.path()?;
+            assert!(path.to_string_lossy().ends_with(".json.gz"));
+
+            let raw = std::fs::read(&path)?;
+            assert_eq!(raw[..2], [0x1f, 0x8b]);
+            assert!(!String::from_utf8_lossy(&raw).contains(TEST_NAME));
+
+            let loaded: TestConfig = load_config()?;
+            assert_eq!(loaded, config);
+
+            // saving unchanged data is a no-op
+            config.save()?;
+            assert_eq!(std::fs::read(&path)?, raw);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_config_toml_context() -> TestResult {
+        use super::formats::{TomlContext, TomlFormat};
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            tags: Vec<String>,
+            pretty: bool,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = TomlFormat;
+            type FormatContext = TomlContext;
+
+            fn format_context(&self) -> TomlContext {
+                TomlContext {
+                    pretty_array: self.pretty,
+                }
+            }
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+        }
+
+        let mut config = TestConfig {
+            tags: vec!["a".into(), "b".into()],
+            pretty: false,
+        };
+        assert_eq!(
+            config.to_string(false)?,
+            "tags = [\"a\", \"b\"]\npretty = false\n"
+        );
+
+        config.pretty = true;
+        assert_eq!(
+            config.to_string(false)?,
+            "tags = [\n    \"a\",\n    \"b\",\n]\npretty = true\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_config_yaml_context() -> TestResult {
+        use super::formats::{YamlContext, YamlFormat};
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        enum Theme {
+            #[default]
+            Dark,
+        }
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            theme: Theme,
+            #[serde(skip)]
+            tagged: bool,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = YamlFormat;
+            type FormatContext = YamlContext;
+
+            fn format_context(&self) -> YamlContext {
+                YamlContext {
+                    tag_unit_variants: self.tagged,
+                }
+            }
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+        }
+
+        let mut config = TestConfig::default();
+        assert_eq!(config.to_string(false)?, "theme: Dark\n");
+
+        config.tagged = true;
+        assert_eq!(config.to_string(false)?, "theme: !Dark\n");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_verify_on_write() -> TestResult {
+        use super::{errors::ConfigError, Format};
+        use serde::de::DeserializeOwned;
+        use std::io::Read;
+
+        /// Writes JSON but always reads back the same data
+        struct CorruptingFormat;
+
+        impl Format<()> for CorruptingFormat {
+            const EXTENSION: &'static str = "json";
+
+            type FormatContext = ();
+
+            fn to_string<T>(data: &T, pretty: bool, context: Option<&()>) -> Result<String>
+            where
+                T: Serialize,
+            {
+                super::formats::JsonFormat::to_string(data, pretty, context)
+            }
+
+            fn from_reader<R, T>(_reader: R, _context: Option<&()>) -> Result<T>
+            where
+                R: Read,
+                T: DeserializeOwned,
+            {
+                serde_json::from_str(r#"{"name":"corrupted","age":0}"#)
+                    .map_err(|e| ConfigError::deserialization("json", e))
+            }
+        }
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = CorruptingFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+
+            fn verify_on_write() -> bool {
+                true
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let path = TestConfig::default().path()?;
+            write(&path, "original")?;
+
+            let config = TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+            };
+            assert_eq!(
+                config.save(),
+                Err(ConfigError::VerificationFailed(path.display().to_string()))
+            );
+            assert_eq!(read_to_string(&path)?, "original");
+
+            // the temporary file is cleaned up so the next save isn't blocked
+            assert_eq!(read_dir(temp_dir.path())?.count(), 1);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_lazy_field() -> TestResult {
+        use super::lazy::Lazy;
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            numbers: Lazy<Vec<u32>>,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            let config = TestConfig {
+                name: TEST_NAME.to_string(),
+                numbers: Lazy::new(vec![1, 2, 3])?,
+            };
+            config.save()?;
+
+            let loaded: TestConfig = load_config()?;
+            assert_eq!(loaded, config);
+            assert!(!loaded.numbers.is_loaded());
+
+            let first = loaded.numbers.get()?;
+            assert_eq!(first, &vec![1, 2, 3]);
+            assert!(loaded.numbers.is_loaded());
+            assert!(std::ptr::eq(first, loaded.numbers.get()?));
+
+            let invalid: Lazy<Vec<u32>> = serde_json::from_str("\"not a list\"").unwrap();
+            assert!(invalid.get().is_err());
+            assert!(!invalid.is_loaded());
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_config_overlays() -> TestResult {
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct Server {
+            host: String,
+            port: u16,
+        }
+
+        #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+        struct TestConfig {
+            name: String,
+            age: u8,
+            server: Server,
+        }
+
+        impl Config for TestConfig {
+            type FormatType = super::formats::JsonFormat;
+            type FormatContext = ();
+
+            fn config_path_and_filename(_: &std::path::Path) -> (Option<PathBuf>, &str) {
+                (None, TEST_FILENAME)
+            }
+        }
+
+        let temp_dir = tempdir()?;
+        let temp_path = temp_dir.path().display().to_string();
+        temp_env::with_var("HOME", Some(temp_path), || {
+            TestConfig {
+                name: TEST_NAME.to_string(),
+                age: TEST_AGE,
+                server: Server {
+                    host: "localhost".to_string(),
+                    port: 8080,
+                },
+            }
+            .save()?;
+
+            write(
+                temp_dir.path().join(format!("{TEST_FILENAME}.local.json")),
+                r#"{"age": 31, "server": {"port": 9090}}"#,
+            )?;
+            write(
+                temp_dir.path().join(format!("{TEST_FILENAME}.dev.json")),
+                r#"{"age": 32}"#,
+            )?;
+
+            let data: TestConfig = load_config_overlaid(&[
+                &format!("{TEST_FILENAME}.local"),
+                &format!("{TEST_FILENAME}.missing"),
+                &format!("{TEST_FILENAME}.dev"),
+            ])?;
+            assert_eq!(
+                data,
+                TestConfig {
+                    name: TEST_NAME.to_string(),
+                    age: 32,
+                    server: Server {
+                        host: "localhost".to_string(),
+                        port: 9090,
+                    },
+                }
+            );
+
+            // the base file is untouched
+            let base: TestConfig = load_config()?;
+            assert_eq!(base.age, TEST_AGE);
+            Ok(())
+        })
+    }
+
+    fn run_test<T>(original: &T) -> TestResult
     where
         T: Config + Debug,
     {
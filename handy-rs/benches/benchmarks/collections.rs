@@ -2,7 +2,7 @@ use criterion::{BenchmarkGroup, Criterion, black_box, criterion_group, measureme
 use rand::Rng;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use std::collections::{BTreeMap, HashMap};
-use handy::collections::{ConcurrentBTreeMap, ConcurrentHashMap, Map};
+use handy::collections::{ConcurrentBTreeMap, ConcurrentHashMap, Map, ShardedConcurrentHashMap};
 
 fn bench_hash_map(c: &mut Criterion) {
     let mut g = c.benchmark_group("HashMap");
@@ -58,12 +58,39 @@ fn bench_concurrent_hash_map(c: &mut Criterion) {
     bench_map::<ConcurrentHashMap<_, _>>(&mut g);
 }
 
+fn bench_concurrent_hash_map_capacity(c: &mut Criterion) {
+    let mut g = c.benchmark_group("ConcurrentHashMap capacity");
+    let len = 100_000;
+
+    g.bench_function("extend", |b| {
+        b.iter(|| {
+            let map = ConcurrentHashMap::new();
+            map.extend((0..len).map(|i| (black_box(i), i))).unwrap();
+            map
+        });
+    });
+
+    g.bench_function("with_capacity extend", |b| {
+        b.iter(|| {
+            let map = ConcurrentHashMap::with_capacity(len);
+            map.extend((0..len).map(|i| (black_box(i), i))).unwrap();
+            map
+        });
+    });
+}
+
 fn bench_concurrent_btree_map(c: &mut Criterion) {
     let mut g = c.benchmark_group("ConcurrentBTreeMap");
 
     bench_map::<ConcurrentBTreeMap<_, _>>(&mut g);
 }
 
+fn bench_sharded_concurrent_hash_map(c: &mut Criterion) {
+    let mut g = c.benchmark_group("ShardedConcurrentHashMap");
+
+    bench_map::<ShardedConcurrentHashMap<_, _>>(&mut g);
+}
+
 fn bench_map<M>(g: &mut BenchmarkGroup<'_, WallTime>)
 where
     M: Map<usize, u32> + Send + Sync,
@@ -101,5 +128,7 @@ criterion_group!(
     bench_hash_map,
     bench_btree_map,
     bench_concurrent_hash_map,
-    bench_concurrent_btree_map
+    bench_concurrent_hash_map_capacity,
+    bench_concurrent_btree_map,
+    bench_sharded_concurrent_hash_map
 );
@@ -3,7 +3,7 @@ use std::path::Path;
 use criterion::{black_box, criterion_group, Criterion};
 use handy::pattern::{
     glob_to_regex_pattern, is_close_to_upper_bound, match_filename_with_glob_pattern, match_string,
-    ERROR_MARGIN,
+    GlobMatcher, ERROR_MARGIN,
 };
 
 fn bench_pattern(c: &mut Criterion) {
@@ -29,6 +29,11 @@ fn bench_pattern(c: &mut Criterion) {
         });
     });
 
+    let matcher = GlobMatcher::new("f*.txt").unwrap();
+    g.bench_function("GlobMatcher::is_match", |b| {
+        b.iter(|| black_box(matcher.is_match(black_box(Path::new("fish.txt")))));
+    });
+
     g.bench_function("match_string", |b| {
         b.iter(|| black_box(match_string(black_box("kitten"), black_box("kissing"))));
     });
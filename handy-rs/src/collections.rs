@@ -1,36 +1,108 @@
 use crate::errors::ConcurrentCollectionError;
+#[cfg(feature = "lru")]
+use hashlink::LruCache;
+#[cfg(feature = "lru")]
+use std::sync::Mutex;
 use std::{
-    collections::{BTreeMap, HashMap},
-    hash::Hash,
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::{BuildHasher, Hash, RandomState},
+    num::NonZeroUsize,
     sync::{Arc, RwLock},
+    thread::available_parallelism,
 };
 
+/// The capacity used by [`LruHashMap::new`] and [`LruHashMap::default`].
+#[cfg(feature = "lru")]
+pub const DEFAULT_LRU_CAPACITY: usize = 1024;
+
 /// A map that can be used to store key-value pairs.
 pub trait Map<K, V>: Default {
     /// Creates a new empty map
     fn new() -> Self;
 
-    /// Inserts a key-value pair into the map.
+    /// Inserts a key-value pair into the map, returning the value previously stored for the key if any.
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn insert(&self, key: K, value: V) -> Result<Option<V>, ConcurrentCollectionError>;
+
+    /// Inserts every key-value pair from an iterator into the map, taking the lock once instead of once per pair.
     ///
     /// ## Errors
     ///
     /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
-    fn insert(&self, key: K, value: V) -> Result<(), ConcurrentCollectionError>;
+    fn extend<I>(&self, iter: I) -> Result<(), ConcurrentCollectionError>
+    where
+        I: IntoIterator<Item = (K, V)>;
 
     /// Retrieves a value from the map.
-    fn get(&self, key: &K) -> Option<V>;
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn get(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError>;
+
+    /// Retrieves a value from the map, inserting the value returned by `f` first if the key is not present.
+    ///
+    /// The lock is held for the whole operation so `f` is only called once per missing key, even if other threads are
+    /// calling this method with the same key.
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn get_or_insert_with<F>(&self, key: K, f: F) -> Result<V, ConcurrentCollectionError>
+    where
+        F: FnOnce() -> V;
+
+    /// Applies `f` to the value of a key in place while holding the lock, so read-modify-write operations like
+    /// incrementing a counter are atomic.
+    ///
+    /// Returns `true` if the key exists, `false` if it doesn't.
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn update<F>(&self, key: &K, f: F) -> Result<bool, ConcurrentCollectionError>
+    where
+        F: FnOnce(&mut V);
 
     /// Removes a key-value pair from the map.
-    fn remove(&self, key: &K) -> Option<V>;
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn remove(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError>;
+
+    /// Removes every key-value pair for which `f` returns `false`, while holding the lock.
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn retain<F>(&self, f: F) -> Result<(), ConcurrentCollectionError>
+    where
+        F: FnMut(&K, &V) -> bool;
 
     /// Returns the number of key-value pairs in the map
-    fn len(&self) -> usize;
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn len(&self) -> Result<usize, ConcurrentCollectionError>;
 
     /// Returns true if the map contains no key-value pairs
-    fn is_empty(&self) -> bool;
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn is_empty(&self) -> Result<bool, ConcurrentCollectionError>;
 
     /// Returns true if the map contains the specified key
-    fn contains_key(&self, key: &K) -> bool;
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn contains_key(&self, key: &K) -> Result<bool, ConcurrentCollectionError>;
 }
 
 /// A concurrent map that can be used to store key-value pairs.
@@ -59,11 +131,117 @@ impl<K, V> Default for ConcurrentHashMap<K, V> {
     }
 }
 
+impl<K, V> ConcurrentHashMap<K, V> {
+    /// Creates a new empty [`ConcurrentHashMap`] with space for at least `capacity` key-value pairs, so bulk loads of
+    /// a known size don't have to grow the map while holding the lock.
+    ///
+    /// ## Arguments
+    ///
+    /// * `capacity` - The number of key-value pairs to allocate space for
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentHashMap, Map};
+    ///
+    /// let map: ConcurrentHashMap<u32, u32> = ConcurrentHashMap::with_capacity(1000);
+    /// map.extend((0..1000).map(|i| (i, i))).unwrap();
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        ConcurrentHashMap {
+            map: Arc::new(RwLock::new(HashMap::with_capacity(capacity))),
+        }
+    }
+
+    /// Returns the number of key-value pairs the map can hold without reallocating
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ConcurrentHashMap;
+    ///
+    /// let map: ConcurrentHashMap<u32, u32> = ConcurrentHashMap::with_capacity(1000);
+    /// assert!(map.capacity().unwrap() >= 1000);
+    /// ```
+    pub fn capacity(&self) -> Result<usize, ConcurrentCollectionError> {
+        match self.map.read() {
+            Ok(guard) => Ok(guard.capacity()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Creates a [`ConcurrentHashMap`] from an existing shared map, any changes made through either are visible to the other.
+    ///
+    /// ## Arguments
+    ///
+    /// * `inner` - The shared map
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentHashMap, Map};
+    /// use std::{collections::HashMap, sync::{Arc, RwLock}};
+    ///
+    /// let inner = Arc::new(RwLock::new(HashMap::new()));
+    /// let map = ConcurrentHashMap::from_inner(Arc::clone(&inner));
+    ///
+    /// map.insert("key", 1).unwrap();
+    /// assert_eq!(inner.read().unwrap().get("key"), Some(&1));
+    /// ```
+    #[must_use]
+    pub fn from_inner(inner: Arc<RwLock<HashMap<K, V>>>) -> Self {
+        ConcurrentHashMap { map: inner }
+    }
+
+    /// Creates a new handle to the same map, the data is not cloned so changes made through either handle are visible to both.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentHashMap, Map};
+    ///
+    /// let map: ConcurrentHashMap<&'static str, u32> = ConcurrentHashMap::new();
+    /// let handle = map.clone_handle();
+    ///
+    /// handle.insert("key", 1).unwrap();
+    /// assert_eq!(map.get(&"key").unwrap(), Some(1));
+    /// ```
+    #[must_use]
+    pub fn clone_handle(&self) -> Self {
+        ConcurrentHashMap {
+            map: Arc::clone(&self.map),
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ConcurrentHashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let iter = iter.into_iter();
+        let mut map = HashMap::with_capacity(iter.size_hint().0);
+        map.extend(iter);
+
+        ConcurrentHashMap {
+            map: Arc::new(RwLock::new(map)),
+        }
+    }
+}
+
 // impl<K, V> ConcurrentHashMap<K, V>
 impl<K, V> Map<K, V> for ConcurrentHashMap<K, V>
 where
     K: Eq + Hash + Send + Sync,
-    V: Copy,
+    V: Clone,
 {
     /// Creates a new empty [`crate::collections::ConcurrentHashMap`]
     ///
@@ -87,16 +265,44 @@ where
     ///
     /// let map: ConcurrentHashMap<&'static str, &'static str> = ConcurrentHashMap::new();
     ///
-    /// map.insert("key", "value").unwrap();
+    /// assert_eq!(map.insert("key", "value").unwrap(), None);
+    /// assert_eq!(map.insert("key", "other").unwrap(), Some("value"));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn insert(&self, key: K, value: V) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.map.write() {
+            Ok(mut guard) => Ok(guard.insert(key, value)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Inserts every key-value pair from an iterator into the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentHashMap, Map};
+    ///
+    /// let map: ConcurrentHashMap<u32, &'static str> = ConcurrentHashMap::new();
+    ///
+    /// map.extend([(1, "one"), (2, "two")]).unwrap();
     /// ```
     ///
     /// ## Errors
     ///
     /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
-    fn insert(&self, key: K, value: V) -> Result<(), ConcurrentCollectionError> {
+    fn extend<I>(&self, iter: I) -> Result<(), ConcurrentCollectionError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
         match self.map.write() {
             Ok(mut guard) => {
-                guard.insert(key, value);
+                let iter = iter.into_iter();
+                guard.reserve(iter.size_hint().0);
+                guard.extend(iter);
                 Ok(())
             }
             Err(_) => Err(ConcurrentCollectionError::Poison),
@@ -114,10 +320,96 @@ where
     ///
     /// map.get(&"key").unwrap();
     /// ```
-    fn get(&self, key: &K) -> Option<V> {
+    fn get(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
         match self.map.read() {
-            Ok(guard) => guard.get(key).copied(),
-            Err(_) => None,
+            Ok(guard) => Ok(guard.get(key).cloned()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Retrieves a value from the map, inserting the value returned by `f` first if the key is not present.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentHashMap, Map};
+    ///
+    /// let map: ConcurrentHashMap<&'static str, &'static str> = ConcurrentHashMap::new();
+    ///
+    /// assert_eq!(map.get_or_insert_with("key", || "value").unwrap(), "value");
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn get_or_insert_with<F>(&self, key: K, f: F) -> Result<V, ConcurrentCollectionError>
+    where
+        F: FnOnce() -> V,
+    {
+        match self.map.write() {
+            Ok(mut guard) => Ok(guard.entry(key).or_insert_with(f).clone()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Applies `f` to the value of a key in place while holding the lock.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentHashMap, Map};
+    ///
+    /// let map: ConcurrentHashMap<u32, u32> = ConcurrentHashMap::new();
+    /// map.insert(1, 2).unwrap();
+    ///
+    /// assert!(map.update(&1, |v| *v += 1).unwrap());
+    /// assert_eq!(map.get(&1).unwrap(), Some(3));
+    /// assert!(!map.update(&2, |v| *v += 1).unwrap());
+    /// ```
+    fn update<F>(&self, key: &K, f: F) -> Result<bool, ConcurrentCollectionError>
+    where
+        F: FnOnce(&mut V),
+    {
+        match self.map.write() {
+            Ok(mut guard) => match guard.get_mut(key) {
+                Some(value) => {
+                    f(value);
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Removes every key-value pair for which `f` returns `false`, while holding the lock.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentHashMap, Map};
+    ///
+    /// let map: ConcurrentHashMap<u32, u32> = ConcurrentHashMap::new();
+    /// map.insert(1, 1).unwrap();
+    /// map.insert(2, 2).unwrap();
+    ///
+    /// map.retain(|k, _| k % 2 == 0).unwrap();
+    /// assert!(!map.contains_key(&1).unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn retain<F>(&self, mut f: F) -> Result<(), ConcurrentCollectionError>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        match self.map.write() {
+            Ok(mut guard) => {
+                guard.retain(|k, v| f(k, v));
+                Ok(())
+            }
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
@@ -133,10 +425,10 @@ where
     /// map.insert("key", "value").unwrap();
     /// map.remove(&"key").unwrap();
     /// ```
-    fn remove(&self, key: &K) -> Option<V> {
+    fn remove(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
         match self.map.write() {
-            Ok(mut guard) => guard.remove(key),
-            Err(_) => None,
+            Ok(mut guard) => Ok(guard.remove(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
@@ -150,11 +442,11 @@ where
     /// let map: ConcurrentHashMap<&'static str, &'static str> = ConcurrentHashMap::new();
     ///
     /// map.insert("key", "value").unwrap();
-    /// assert_eq!(map.len(), 1);
-    fn len(&self) -> usize {
+    /// assert_eq!(map.len().unwrap(), 1);
+    fn len(&self) -> Result<usize, ConcurrentCollectionError> {
         match self.map.read() {
-            Ok(guard) => guard.len(),
-            Err(_) => 0,
+            Ok(guard) => Ok(guard.len()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
@@ -167,15 +459,15 @@ where
     ///
     /// let map: ConcurrentHashMap<&'static str, &'static str> = ConcurrentHashMap::new();
     ///
-    /// assert!(map.is_empty());
+    /// assert!(map.is_empty().unwrap());
     ///
     /// map.insert("key", "value").unwrap();
-    /// assert!(!map.is_empty());
+    /// assert!(!map.is_empty().unwrap());
     /// ```
-    fn is_empty(&self) -> bool {
+    fn is_empty(&self) -> Result<bool, ConcurrentCollectionError> {
         match self.map.read() {
-            Ok(guard) => guard.is_empty(),
-            Err(_) => false,
+            Ok(guard) => Ok(guard.is_empty()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
@@ -189,12 +481,12 @@ where
     /// let map: ConcurrentHashMap<&'static str, &'static str> = ConcurrentHashMap::new();
     ///
     /// map.insert("key", "value").unwrap();
-    /// assert!(map.contains_key(&"key"));
+    /// assert!(map.contains_key(&"key").unwrap());
     /// ```
-    fn contains_key(&self, key: &K) -> bool {
+    fn contains_key(&self, key: &K) -> Result<bool, ConcurrentCollectionError> {
         match self.map.read() {
-            Ok(guard) => guard.contains_key(key),
-            Err(_) => false,
+            Ok(guard) => Ok(guard.contains_key(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 }
@@ -225,10 +517,46 @@ impl<K, V> Default for ConcurrentBTreeMap<K, V> {
     }
 }
 
+impl<K, V> ConcurrentBTreeMap<K, V> {
+    /// Creates a new empty [`ConcurrentBTreeMap`], `capacity` is ignored since a [`BTreeMap`] allocates its nodes as
+    /// they're needed. It exists so code can switch between [`ConcurrentHashMap`] and [`ConcurrentBTreeMap`] easily.
+    ///
+    /// ## Arguments
+    ///
+    /// * `capacity` - Ignored
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentBTreeMap, Map};
+    ///
+    /// let map: ConcurrentBTreeMap<u32, u32> = ConcurrentBTreeMap::with_capacity(1000);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let _ = capacity;
+        ConcurrentBTreeMap::default()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ConcurrentBTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        ConcurrentBTreeMap {
+            map: Arc::new(RwLock::new(iter.into_iter().collect())),
+        }
+    }
+}
+
 impl<K, V> Map<K, V> for ConcurrentBTreeMap<K, V>
 where
     K: Eq + Ord + Send + Sync,
-    V: Copy,
+    V: Clone,
 {
     /// Creates a new concurrent map.
     ///
@@ -258,37 +586,42 @@ where
     /// ## Errors
     ///
     /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
-    fn insert(&self, key: K, value: V) -> Result<(), ConcurrentCollectionError> {
+    fn insert(&self, key: K, value: V) -> Result<Option<V>, ConcurrentCollectionError> {
         match self.map.write() {
-            Ok(mut guard) => {
-                guard.insert(key, value);
-                Ok(())
-            }
+            Ok(mut guard) => Ok(guard.insert(key, value)),
             Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
-    /// Retrieves the value associated with the specified key.
+    /// Inserts every key-value pair from an iterator into the map.
     ///
     /// ## Examples
     ///
     /// ```rust,no_run
     /// use handy::collections::{ConcurrentBTreeMap, Map};
     ///
-    /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
+    /// let map: ConcurrentBTreeMap<u32, &'static str> = ConcurrentBTreeMap::new();
     ///
-    /// if let Some(value) = map.get(&1) {
-    ///     // do something
-    /// }
+    /// map.extend([(1, "one"), (2, "two")]).unwrap();
     /// ```
-    fn get(&self, key: &K) -> Option<V> {
-        match self.map.read() {
-            Ok(guard) => guard.get(key).copied(),
-            Err(_) => None,
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn extend<I>(&self, iter: I) -> Result<(), ConcurrentCollectionError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        match self.map.write() {
+            Ok(mut guard) => {
+                guard.extend(iter);
+                Ok(())
+            }
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
-    /// Checks if the map contains the specified key.
+    /// Retrieves the value associated with the specified key.
     ///
     /// ## Examples
     ///
@@ -297,18 +630,18 @@ where
     ///
     /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
     ///
-    /// if map.contains_key(&1) {
+    /// if let Some(value) = map.get(&1).unwrap() {
     ///     // do something
     /// }
     /// ```
-    fn contains_key(&self, key: &K) -> bool {
+    fn get(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
         match self.map.read() {
-            Ok(guard) => guard.contains_key(key),
-            Err(_) => false,
+            Ok(guard) => Ok(guard.get(key).cloned()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
-    /// Removes the value associated with the specified key.
+    /// Retrieves a value from the map, inserting the value returned by `f` first if the key is not present.
     ///
     /// ## Examples
     ///
@@ -317,50 +650,1124 @@ where
     ///
     /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
     ///
-    /// map.remove(&1).unwrap();
+    /// assert_eq!(map.get_or_insert_with(1, || 2).unwrap(), 2);
     /// ```
-    fn remove(&self, key: &K) -> Option<V> {
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn get_or_insert_with<F>(&self, key: K, f: F) -> Result<V, ConcurrentCollectionError>
+    where
+        F: FnOnce() -> V,
+    {
         match self.map.write() {
-            Ok(mut guard) => guard.remove(key),
-            Err(_) => None,
+            Ok(mut guard) => Ok(guard.entry(key).or_insert_with(f).clone()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 
-    /// Returns the number of key-value pairs in the map.
+    /// Applies `f` to the value of a key in place while holding the lock.
     ///
-    /// ## Example
+    /// ## Examples
     ///
     /// ```rust,no_run
     /// use handy::collections::{ConcurrentBTreeMap, Map};
     ///
-    /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
-    ///
+    /// let map: ConcurrentBTreeMap<u32, u32> = ConcurrentBTreeMap::new();
     /// map.insert(1, 2).unwrap();
-    /// assert_eq!(map.len(), 1);
+    ///
+    /// assert!(map.update(&1, |v| *v += 1).unwrap());
+    /// assert_eq!(map.get(&1).unwrap(), Some(3));
+    /// assert!(!map.update(&2, |v| *v += 1).unwrap());
     /// ```
-    fn len(&self) -> usize {
-        match self.map.read() {
-            Ok(guard) => guard.len(),
-            Err(_) => 0,
-        }
+    fn update<F>(&self, key: &K, f: F) -> Result<bool, ConcurrentCollectionError>
+    where
+        F: FnOnce(&mut V),
+    {
+        match self.map.write() {
+            Ok(mut guard) => match guard.get_mut(key) {
+                Some(value) => {
+                    f(value);
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Checks if the map contains the specified key.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentBTreeMap, Map};
+    ///
+    /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
+    ///
+    /// if map.contains_key(&1).unwrap() {
+    ///     // do something
+    /// }
+    /// ```
+    fn contains_key(&self, key: &K) -> Result<bool, ConcurrentCollectionError> {
+        match self.map.read() {
+            Ok(guard) => Ok(guard.contains_key(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Removes every key-value pair for which `f` returns `false`, while holding the lock. The remaining keys keep their order.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentBTreeMap, Map};
+    ///
+    /// let map: ConcurrentBTreeMap<u32, u32> = ConcurrentBTreeMap::new();
+    /// map.insert(1, 1).unwrap();
+    /// map.insert(2, 2).unwrap();
+    ///
+    /// map.retain(|k, _| k % 2 == 0).unwrap();
+    /// assert!(!map.contains_key(&1).unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn retain<F>(&self, mut f: F) -> Result<(), ConcurrentCollectionError>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        match self.map.write() {
+            Ok(mut guard) => {
+                guard.retain(|k, v| f(k, v));
+                Ok(())
+            }
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Removes the value associated with the specified key.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentBTreeMap, Map};
+    ///
+    /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
+    ///
+    /// map.remove(&1).unwrap();
+    /// ```
+    fn remove(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.map.write() {
+            Ok(mut guard) => Ok(guard.remove(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns the number of key-value pairs in the map.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentBTreeMap, Map};
+    ///
+    /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
+    ///
+    /// map.insert(1, 2).unwrap();
+    /// assert_eq!(map.len().unwrap(), 1);
+    /// ```
+    fn len(&self) -> Result<usize, ConcurrentCollectionError> {
+        match self.map.read() {
+            Ok(guard) => Ok(guard.len()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Checks if the map is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ConcurrentBTreeMap, Map};
+    ///
+    /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
+    ///
+    /// map.insert(1, 2).unwrap();
+    /// assert!(!map.is_empty().unwrap());
+    /// ```
+    fn is_empty(&self) -> Result<bool, ConcurrentCollectionError> {
+        match self.map.read() {
+            Ok(guard) => Ok(guard.is_empty()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+}
+
+/// A concurrent hash map split into shards, each with its own lock, so threads working on keys in different shards
+/// don't block each other.
+///
+/// Keys are assigned to a shard by their hash, operations on a single key only lock its shard while [`Map::len`] and
+/// [`Map::is_empty`] lock every shard one at a time.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::collections::{Map, ShardedConcurrentHashMap};
+///
+/// let map: ShardedConcurrentHashMap<u32, &'static str> = ShardedConcurrentHashMap::with_shards(16);
+/// ```
+///
+/// ## Errors
+///
+/// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+#[derive(Debug)]
+pub struct ShardedConcurrentHashMap<K, V> {
+    shards: Box<[RwLock<HashMap<K, V>>]>,
+    hasher: RandomState,
+}
+
+impl<K, V> ShardedConcurrentHashMap<K, V> {
+    /// Creates a new empty [`ShardedConcurrentHashMap`] with the given number of shards.
+    ///
+    /// ## Arguments
+    ///
+    /// * `shards` - The number of shards
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `shards` is `0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ShardedConcurrentHashMap;
+    ///
+    /// let map: ShardedConcurrentHashMap<u32, u32> = ShardedConcurrentHashMap::with_shards(16);
+    /// ```
+    #[must_use]
+    pub fn with_shards(shards: usize) -> Self {
+        assert!(shards > 0, "Shard count must be greater than 0");
+
+        ShardedConcurrentHashMap {
+            shards: (0..shards).map(|_| RwLock::new(HashMap::new())).collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Returns the number of shards.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ShardedConcurrentHashMap;
+    ///
+    /// let map: ShardedConcurrentHashMap<u32, u32> = ShardedConcurrentHashMap::with_shards(16);
+    /// assert_eq!(map.shard_count(), 16);
+    /// ```
+    #[must_use]
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the shard the key belongs to.
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V>>
+    where
+        K: Hash,
+    {
+        &self.shards[self.shard_index(key)]
+    }
+
+    /// Returns the index of the shard the key belongs to.
+    fn shard_index(&self, key: &K) -> usize
+    where
+        K: Hash,
+    {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = (self.hasher.hash_one(key) % self.shards.len() as u64) as usize;
+        index
+    }
+}
+
+impl<K, V> Default for ShardedConcurrentHashMap<K, V> {
+    /// Creates a new empty [`ShardedConcurrentHashMap`] with one shard per available thread.
+    fn default() -> Self {
+        ShardedConcurrentHashMap::with_shards(available_parallelism().map_or(1, NonZeroUsize::get))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ShardedConcurrentHashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = ShardedConcurrentHashMap::default();
+        for (key, value) in iter {
+            let index = map.shard_index(&key);
+
+            // the map isn't shared yet so the locks can't be poisoned
+            if let Ok(shard) = map.shards[index].get_mut() {
+                shard.insert(key, value);
+            }
+        }
+        map
+    }
+}
+
+impl<K, V> Map<K, V> for ShardedConcurrentHashMap<K, V>
+where
+    K: Eq + Hash + Send + Sync,
+    V: Clone,
+{
+    /// Creates a new empty [`ShardedConcurrentHashMap`] with one shard per available thread.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    /// ```
+    fn new() -> Self {
+        ShardedConcurrentHashMap::default()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    ///
+    /// assert_eq!(map.insert("key", "value").unwrap(), None);
+    /// assert_eq!(map.insert("key", "other").unwrap(), Some("value"));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn insert(&self, key: K, value: V) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.shard(&key).write() {
+            Ok(mut guard) => Ok(guard.insert(key, value)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Inserts every key-value pair from an iterator into the map, locking each shard once.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ShardedConcurrentHashMap, Map};
+    ///
+    /// let map: ShardedConcurrentHashMap<u32, &'static str> = ShardedConcurrentHashMap::new();
+    ///
+    /// map.extend([(1, "one"), (2, "two")]).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn extend<I>(&self, iter: I) -> Result<(), ConcurrentCollectionError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut buckets: Vec<Vec<(K, V)>> = self.shards.iter().map(|_| Vec::new()).collect();
+        for (key, value) in iter {
+            buckets[self.shard_index(&key)].push((key, value));
+        }
+
+        for (shard, bucket) in self.shards.iter().zip(buckets) {
+            if bucket.is_empty() {
+                continue;
+            }
+
+            match shard.write() {
+                Ok(mut guard) => guard.extend(bucket),
+                Err(_) => return Err(ConcurrentCollectionError::Poison),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves a value from the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    /// map.insert("key", "value").unwrap();
+    ///
+    /// assert_eq!(map.get(&"key").unwrap(), Some("value"));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn get(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.shard(key).read() {
+            Ok(guard) => Ok(guard.get(key).cloned()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Retrieves a value from the map, inserting the value returned by `f` first if the key is not present.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    ///
+    /// assert_eq!(map.get_or_insert_with("key", || "value").unwrap(), "value");
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn get_or_insert_with<F>(&self, key: K, f: F) -> Result<V, ConcurrentCollectionError>
+    where
+        F: FnOnce() -> V,
+    {
+        match self.shard(&key).write() {
+            Ok(mut guard) => Ok(guard.entry(key).or_insert_with(f).clone()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Applies `f` to the value of a key in place while holding the lock of its shard.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<u32, u32> = ShardedConcurrentHashMap::new();
+    /// map.insert(1, 2).unwrap();
+    ///
+    /// assert!(map.update(&1, |v| *v += 1).unwrap());
+    /// assert_eq!(map.get(&1).unwrap(), Some(3));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn update<F>(&self, key: &K, f: F) -> Result<bool, ConcurrentCollectionError>
+    where
+        F: FnOnce(&mut V),
+    {
+        match self.shard(key).write() {
+            Ok(mut guard) => match guard.get_mut(key) {
+                Some(value) => {
+                    f(value);
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Removes every key-value pair for which `f` returns `false`, while holding the lock. The shards are locked one at a time.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{ShardedConcurrentHashMap, Map};
+    ///
+    /// let map: ShardedConcurrentHashMap<u32, u32> = ShardedConcurrentHashMap::new();
+    /// map.insert(1, 1).unwrap();
+    /// map.insert(2, 2).unwrap();
+    ///
+    /// map.retain(|k, _| k % 2 == 0).unwrap();
+    /// assert!(!map.contains_key(&1).unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn retain<F>(&self, mut f: F) -> Result<(), ConcurrentCollectionError>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        for shard in &*self.shards {
+            match shard.write() {
+                Ok(mut guard) => guard.retain(|k, v| f(k, v)),
+                Err(_) => return Err(ConcurrentCollectionError::Poison),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes a key-value pair from the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    /// map.insert("key", "value").unwrap();
+    ///
+    /// assert_eq!(map.remove(&"key").unwrap(), Some("value"));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn remove(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.shard(key).write() {
+            Ok(mut guard) => Ok(guard.remove(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns the number of key-value pairs in the map, the shards are counted one at a time so concurrent writes
+    /// may or may not be included.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    /// map.insert("key", "value").unwrap();
+    ///
+    /// assert_eq!(map.len().unwrap(), 1);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock of any shard is poisoned
+    fn len(&self) -> Result<usize, ConcurrentCollectionError> {
+        self.shards
+            .iter()
+            .try_fold(0, |len, shard| match shard.read() {
+                Ok(guard) => Ok(len + guard.len()),
+                Err(_) => Err(ConcurrentCollectionError::Poison),
+            })
+    }
+
+    /// Returns true if the map contains no key-value pairs.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    ///
+    /// assert!(map.is_empty().unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock of any shard is poisoned
+    fn is_empty(&self) -> Result<bool, ConcurrentCollectionError> {
+        for shard in &*self.shards {
+            match shard.read() {
+                Ok(guard) if !guard.is_empty() => return Ok(false),
+                Ok(_) => {}
+                Err(_) => return Err(ConcurrentCollectionError::Poison),
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns true if the map contains the specified key.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{Map, ShardedConcurrentHashMap};
+    ///
+    /// let map: ShardedConcurrentHashMap<&'static str, &'static str> = ShardedConcurrentHashMap::new();
+    /// map.insert("key", "value").unwrap();
+    ///
+    /// assert!(map.contains_key(&"key").unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn contains_key(&self, key: &K) -> Result<bool, ConcurrentCollectionError> {
+        match self.shard(key).read() {
+            Ok(guard) => Ok(guard.contains_key(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+}
+
+/// A concurrent set that can be used to store unique values.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::collections::ConcurrentHashSet;
+///
+/// let set: ConcurrentHashSet<u32> = ConcurrentHashSet::new();
+///
+/// assert!(set.insert(1).unwrap());
+/// assert!(!set.insert(1).unwrap());
+/// ```
+///
+/// ## Errors
+///
+/// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+#[derive(Debug)]
+pub struct ConcurrentHashSet<T> {
+    set: Arc<RwLock<HashSet<T>>>,
+}
+
+impl<T> Default for ConcurrentHashSet<T> {
+    fn default() -> Self {
+        ConcurrentHashSet {
+            set: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+}
+
+impl<T> ConcurrentHashSet<T>
+where
+    T: Eq + Hash,
+{
+    /// Creates a new empty [`ConcurrentHashSet`]
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use handy::collections::ConcurrentHashSet;
+    ///
+    /// let set: ConcurrentHashSet<&'static str> = ConcurrentHashSet::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        ConcurrentHashSet::default()
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the value was not in the set already
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ConcurrentHashSet;
+    ///
+    /// let set: ConcurrentHashSet<&'static str> = ConcurrentHashSet::new();
+    ///
+    /// assert!(set.insert("value").unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    pub fn insert(&self, value: T) -> Result<bool, ConcurrentCollectionError> {
+        match self.set.write() {
+            Ok(mut guard) => Ok(guard.insert(value)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns true if the set contains the value.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ConcurrentHashSet;
+    ///
+    /// let set: ConcurrentHashSet<&'static str> = ConcurrentHashSet::new();
+    /// set.insert("value").unwrap();
+    ///
+    /// assert!(set.contains(&"value").unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    pub fn contains(&self, value: &T) -> Result<bool, ConcurrentCollectionError> {
+        match self.set.read() {
+            Ok(guard) => Ok(guard.contains(value)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Removes a value from the set.
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the value was in the set
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ConcurrentHashSet;
+    ///
+    /// let set: ConcurrentHashSet<&'static str> = ConcurrentHashSet::new();
+    /// set.insert("value").unwrap();
+    ///
+    /// assert!(set.remove(&"value").unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    pub fn remove(&self, value: &T) -> Result<bool, ConcurrentCollectionError> {
+        match self.set.write() {
+            Ok(mut guard) => Ok(guard.remove(value)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns the number of values in the set.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ConcurrentHashSet;
+    ///
+    /// let set: ConcurrentHashSet<&'static str> = ConcurrentHashSet::new();
+    /// set.insert("value").unwrap();
+    ///
+    /// assert_eq!(set.len().unwrap(), 1);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    pub fn len(&self) -> Result<usize, ConcurrentCollectionError> {
+        match self.set.read() {
+            Ok(guard) => Ok(guard.len()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns true if the set contains no values.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ConcurrentHashSet;
+    ///
+    /// let set: ConcurrentHashSet<&'static str> = ConcurrentHashSet::new();
+    ///
+    /// assert!(set.is_empty().unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    pub fn is_empty(&self) -> Result<bool, ConcurrentCollectionError> {
+        match self.set.read() {
+            Ok(guard) => Ok(guard.is_empty()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns a copy of the values currently in the set, changes made to the set afterwards are not reflected in it.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::ConcurrentHashSet;
+    ///
+    /// let set: ConcurrentHashSet<&'static str> = ConcurrentHashSet::new();
+    /// set.insert("value").unwrap();
+    ///
+    /// let snapshot = set.snapshot().unwrap();
+    /// assert!(snapshot.contains("value"));
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    pub fn snapshot(&self) -> Result<HashSet<T>, ConcurrentCollectionError>
+    where
+        T: Clone,
+    {
+        match self.set.read() {
+            Ok(guard) => Ok(guard.clone()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+}
+
+/// A bounded concurrent map that evicts the least-recently-used entry when full.
+///
+/// Both [`Map::insert`] and [`Map::get`] mark the key as the most recently used, so unlike the other maps every operation takes an exclusive lock.
+///
+/// Requires the `lru` feature.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::collections::{LruHashMap, Map};
+///
+/// let map: LruHashMap<u32, &'static str> = LruHashMap::with_capacity(2);
+///
+/// map.insert(1, "one").unwrap();
+/// map.insert(2, "two").unwrap();
+/// map.get(&1).unwrap();
+/// map.insert(3, "three").unwrap(); // evicts 2
+///
+/// assert!(!map.contains_key(&2).unwrap());
+/// ```
+///
+/// ## Errors
+///
+/// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+#[cfg(feature = "lru")]
+#[derive(Debug)]
+pub struct LruHashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    map: Arc<Mutex<LruCache<K, V>>>,
+}
+
+#[cfg(feature = "lru")]
+impl<K, V> LruHashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    /// Creates a new empty [`LruHashMap`] that holds at most `capacity` entries.
+    ///
+    /// ## Arguments
+    ///
+    /// * `capacity` - The maximum number of entries in the map
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::LruHashMap;
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(100);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "Capacity must be greater than 0");
+
+        LruHashMap {
+            map: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Returns the maximum number of entries the map can hold.
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::LruHashMap;
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(100);
+    /// assert_eq!(map.capacity().unwrap(), 100);
+    /// ```
+    pub fn capacity(&self) -> Result<usize, ConcurrentCollectionError> {
+        match self.map.lock() {
+            Ok(guard) => Ok(guard.capacity()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+}
+
+#[cfg(feature = "lru")]
+impl<K, V> Default for LruHashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        LruHashMap::with_capacity(DEFAULT_LRU_CAPACITY)
+    }
+}
+
+#[cfg(feature = "lru")]
+impl<K, V> Map<K, V> for LruHashMap<K, V>
+where
+    K: Eq + Hash + Send + Sync,
+    V: Clone,
+{
+    /// Creates a new empty [`LruHashMap`] with a capacity of [`DEFAULT_LRU_CAPACITY`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::new();
+    /// ```
+    fn new() -> Self {
+        LruHashMap::default()
+    }
+
+    /// Inserts a key-value pair into the map, evicting the least-recently-used entry if the map is full. Only the value
+    /// previously stored for the key is returned, not the evicted one.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(1);
+    ///
+    /// map.insert(1, 2).unwrap();
+    /// map.insert(3, 4).unwrap(); // evicts 1
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn insert(&self, key: K, value: V) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.map.lock() {
+            Ok(mut guard) => Ok(guard.insert(key, value)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Inserts every key-value pair from an iterator into the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, &'static str> = LruHashMap::with_capacity(10);
+    ///
+    /// map.extend([(1, "one"), (2, "two")]).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn extend<I>(&self, iter: I) -> Result<(), ConcurrentCollectionError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        match self.map.lock() {
+            Ok(mut guard) => {
+                for (key, value) in iter {
+                    guard.insert(key, value);
+                }
+
+                Ok(())
+            }
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Retrieves a value from the map and marks it as the most recently used.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+    ///
+    /// map.insert(1, 2).unwrap();
+    /// assert_eq!(map.get(&1).unwrap(), Some(2));
+    /// ```
+    fn get(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.map.lock() {
+            Ok(mut guard) => Ok(guard.get(key).cloned()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
     }
 
-    /// Checks if the map is empty.
+    /// Retrieves a value from the map, inserting the value returned by `f` first if the key is not present.
     ///
-    /// ## Example
+    /// ## Examples
     ///
     /// ```rust,no_run
-    /// use handy::collections::{ConcurrentBTreeMap, Map};
+    /// use handy::collections::{LruHashMap, Map};
     ///
-    /// let map: ConcurrentBTreeMap<usize, u32> = ConcurrentBTreeMap::new();
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+    ///
+    /// assert_eq!(map.get_or_insert_with(1, || 2).unwrap(), 2);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn get_or_insert_with<F>(&self, key: K, f: F) -> Result<V, ConcurrentCollectionError>
+    where
+        F: FnOnce() -> V,
+    {
+        match self.map.lock() {
+            Ok(mut guard) => {
+                if let Some(value) = guard.get(&key) {
+                    return Ok(value.clone());
+                }
+
+                let value = f();
+                guard.insert(key, value.clone());
+                Ok(value)
+            }
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Applies `f` to the value of a key in place while holding the lock.
+    ///
+    /// ## Examples
     ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
     /// map.insert(1, 2).unwrap();
-    /// assert!(!map.is_empty());
+    ///
+    /// assert!(map.update(&1, |v| *v += 1).unwrap());
+    /// assert_eq!(map.get(&1).unwrap(), Some(3));
+    /// assert!(!map.update(&2, |v| *v += 1).unwrap());
     /// ```
-    fn is_empty(&self) -> bool {
-        match self.map.read() {
-            Ok(guard) => guard.is_empty(),
-            Err(_) => false,
+    fn update<F>(&self, key: &K, f: F) -> Result<bool, ConcurrentCollectionError>
+    where
+        F: FnOnce(&mut V),
+    {
+        match self.map.lock() {
+            Ok(mut guard) => match guard.get_mut(key) {
+                Some(value) => {
+                    f(value);
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Removes every key-value pair for which `f` returns `false`, while holding the lock. The remaining keys keep their recency order.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+    /// map.insert(1, 1).unwrap();
+    /// map.insert(2, 2).unwrap();
+    ///
+    /// map.retain(|k, _| k % 2 == 0).unwrap();
+    /// assert!(!map.contains_key(&1).unwrap());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// - [`ConcurrentCollectionError::Poison`]: The lock is poisoned
+    fn retain<F>(&self, mut f: F) -> Result<(), ConcurrentCollectionError>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        match self.map.lock() {
+            Ok(mut guard) => {
+                // the cache has no `retain` so the entries are drained and the ones kept are inserted back,
+                // draining goes from least to most recently used so the order is preserved
+                let entries: Vec<(K, V)> = guard.drain().collect();
+                for (key, value) in entries {
+                    if f(&key, &value) {
+                        guard.insert(key, value);
+                    }
+                }
+
+                Ok(())
+            }
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Removes a key-value pair from the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+    ///
+    /// map.insert(1, 2).unwrap();
+    /// map.remove(&1).unwrap();
+    /// ```
+    fn remove(&self, key: &K) -> Result<Option<V>, ConcurrentCollectionError> {
+        match self.map.lock() {
+            Ok(mut guard) => Ok(guard.remove(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns the number of key-value pairs in the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+    ///
+    /// map.insert(1, 2).unwrap();
+    /// assert_eq!(map.len().unwrap(), 1);
+    /// ```
+    fn len(&self) -> Result<usize, ConcurrentCollectionError> {
+        match self.map.lock() {
+            Ok(guard) => Ok(guard.len()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns true if the map contains no key-value pairs.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+    ///
+    /// assert!(map.is_empty().unwrap());
+    /// ```
+    fn is_empty(&self) -> Result<bool, ConcurrentCollectionError> {
+        match self.map.lock() {
+            Ok(guard) => Ok(guard.is_empty()),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
+        }
+    }
+
+    /// Returns true if the map contains the specified key, without updating its recency.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use handy::collections::{LruHashMap, Map};
+    ///
+    /// let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+    ///
+    /// map.insert(1, 2).unwrap();
+    /// assert!(map.contains_key(&1).unwrap());
+    /// ```
+    fn contains_key(&self, key: &K) -> Result<bool, ConcurrentCollectionError> {
+        match self.map.lock() {
+            Ok(guard) => Ok(guard.contains_key(key)),
+            Err(_) => Err(ConcurrentCollectionError::Poison),
         }
     }
 }
@@ -370,17 +1777,423 @@ mod tests {
     use super::*;
     use rand::Rng;
     use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[test]
     fn test_concurrent_hash_map() {
         assert_map_works::<ConcurrentHashMap<_, _>>();
     }
 
+    #[test]
+    fn test_concurrent_hash_map_shared_handles() {
+        let inner = Arc::new(RwLock::new(HashMap::new()));
+        let map = ConcurrentHashMap::from_inner(Arc::clone(&inner));
+        let handle = map.clone_handle();
+
+        map.insert(1, 10).unwrap();
+        assert_eq!(handle.get(&1).unwrap(), Some(10));
+        assert_eq!(inner.read().unwrap().get(&1), Some(&10));
+
+        std::thread::scope(|s| {
+            s.spawn(|| handle.insert(2, 20).unwrap());
+        });
+        assert_eq!(map.get(&2).unwrap(), Some(20));
+
+        inner.write().unwrap().insert(3, 30);
+        assert_eq!(map.get(&3).unwrap(), Some(30));
+        assert_eq!(handle.len().unwrap(), 3);
+
+        map.remove(&1).unwrap();
+        assert!(!handle.contains_key(&1).unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_hash_map_with_capacity() {
+        let map: ConcurrentHashMap<u32, u32> = ConcurrentHashMap::with_capacity(100);
+        let capacity = map.capacity().unwrap();
+        assert!(capacity >= 100);
+
+        map.extend((0..100).map(|i| (i, i * 2))).unwrap();
+        assert_eq!(map.capacity().unwrap(), capacity);
+        assert_eq!(map.len().unwrap(), 100);
+        assert_eq!(map.get(&42).unwrap(), Some(84));
+
+        let collected: ConcurrentHashMap<u32, u32> = (0..100).map(|i| (i, i * 2)).collect();
+        assert!(collected.capacity().unwrap() >= 100);
+        assert!((0..100).all(|i| collected.get(&i).unwrap() == map.get(&i).unwrap()));
+
+        let btree: ConcurrentBTreeMap<u32, u32> = ConcurrentBTreeMap::with_capacity(100);
+        btree.extend((0..100).map(|i| (i, i * 2))).unwrap();
+        assert_eq!(btree.len().unwrap(), 100);
+        assert!((0..100).all(|i| btree.get(&i).unwrap() == map.get(&i).unwrap()));
+    }
+
     #[test]
     fn test_concurrent_btree_map() {
         assert_map_works::<ConcurrentBTreeMap<_, _>>();
     }
 
+    #[test]
+    fn test_sharded_concurrent_hash_map() {
+        assert_map_works::<ShardedConcurrentHashMap<_, _>>();
+    }
+
+    #[test]
+    fn test_sharded_concurrent_hash_map_poison() {
+        let map: ShardedConcurrentHashMap<u32, u32> = ShardedConcurrentHashMap::with_shards(4);
+        let other_key = (2..1000)
+            .find(|k| !std::ptr::eq(map.shard(k), map.shard(&1)))
+            .unwrap();
+        map.insert(1, 1).unwrap();
+
+        std::thread::scope(|s| {
+            let result = s
+                .spawn(|| map.update(&1, |_| panic!("poisoning the lock")))
+                .join();
+            assert!(result.is_err());
+        });
+
+        // only the shard of the key is poisoned but the whole map is needed for these
+        assert_eq!(map.get(&1), Err(ConcurrentCollectionError::Poison));
+        assert_eq!(map.len(), Err(ConcurrentCollectionError::Poison));
+        assert_eq!(map.is_empty(), Err(ConcurrentCollectionError::Poison));
+
+        map.insert(other_key, 2).unwrap();
+        assert_eq!(map.get(&other_key), Ok(Some(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Shard count must be greater than 0")]
+    fn test_sharded_concurrent_hash_map_zero_shards() {
+        let _ = ShardedConcurrentHashMap::<u32, u32>::with_shards(0);
+    }
+
+    #[test]
+    fn test_concurrent_maps_clone_values() {
+        fn assert_clone_values<M: Map<u32, String>>() {
+            let map = M::new();
+            map.insert(1, "one".to_string()).unwrap();
+            map.insert(2, "two".to_string()).unwrap();
+
+            assert_eq!(map.get(&1).unwrap(), Some("one".to_string()));
+            assert_eq!(map.get(&2).unwrap(), Some("two".to_string()));
+            assert_eq!(map.remove(&1).unwrap(), Some("one".to_string()));
+            assert_eq!(map.get(&1).unwrap(), None);
+            assert_eq!(map.len().unwrap(), 1);
+        }
+
+        assert_clone_values::<ConcurrentHashMap<_, _>>();
+        assert_clone_values::<ConcurrentBTreeMap<_, _>>();
+        assert_clone_values::<ShardedConcurrentHashMap<_, _>>();
+        #[cfg(feature = "lru")]
+        assert_clone_values::<LruHashMap<_, _>>();
+    }
+
+    #[test]
+    fn test_concurrent_maps_update() {
+        fn assert_update_works<M: Map<&'static str, usize> + Send + Sync>() {
+            const NUM_THREADS: usize = 10;
+            const OPS_PER_THREAD: usize = 1000;
+
+            let map = M::new();
+            map.insert("counter", 0).unwrap();
+
+            (0..NUM_THREADS).into_par_iter().for_each(|_| {
+                for _ in 0..OPS_PER_THREAD {
+                    assert!(map.update(&"counter", |v| *v += 1).unwrap());
+                }
+            });
+
+            assert_eq!(
+                map.get(&"counter").unwrap(),
+                Some(NUM_THREADS * OPS_PER_THREAD)
+            );
+            assert!(!map.update(&"missing", |v| *v += 1).unwrap());
+            assert_eq!(map.get(&"missing").unwrap(), None);
+        }
+
+        assert_update_works::<ConcurrentHashMap<_, _>>();
+        assert_update_works::<ConcurrentBTreeMap<_, _>>();
+        assert_update_works::<ShardedConcurrentHashMap<_, _>>();
+        #[cfg(feature = "lru")]
+        assert_update_works::<LruHashMap<_, _>>();
+    }
+
+    #[test]
+    fn test_concurrent_hash_set() {
+        const NUM_THREADS: u32 = 10;
+        const RANGE_SIZE: u32 = 1000;
+        const STEP: u32 = 500;
+
+        let set = ConcurrentHashSet::new();
+        let added = AtomicUsize::new(0);
+
+        // every thread inserts a range that overlaps half of the previous one
+        (0..NUM_THREADS).into_par_iter().for_each(|t| {
+            for i in t * STEP..t * STEP + RANGE_SIZE {
+                if set.insert(i).unwrap() {
+                    added.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        });
+
+        let max = (NUM_THREADS - 1) * STEP + RANGE_SIZE;
+        let union = max as usize;
+        assert_eq!(set.len().unwrap(), union);
+        assert_eq!(added.load(Ordering::SeqCst), union);
+        assert!(set.contains(&0).unwrap());
+        assert!(!set.contains(&max).unwrap());
+
+        let snapshot = set.snapshot().unwrap();
+        assert!(set.remove(&0).unwrap());
+        assert!(!set.remove(&0).unwrap());
+        assert_eq!(snapshot.len(), union);
+        assert_eq!(set.len().unwrap(), union - 1);
+        assert!(!set.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_maps_from_iter_and_extend() {
+        fn assert_from_iter_and_extend<M>()
+        where
+            M: Map<u32, u32> + FromIterator<(u32, u32)>,
+        {
+            let collected: M = (0..100).map(|i| (i, i * 2)).collect();
+            assert_eq!(collected.len().unwrap(), 100);
+            for i in 0..100 {
+                assert_eq!(collected.get(&i).unwrap(), Some(i * 2));
+            }
+
+            let extended = M::new();
+            extended.insert(0, 1).unwrap();
+            extended.extend((0..100).map(|i| (i, i * 3))).unwrap();
+            assert_eq!(extended.len().unwrap(), 100);
+            for i in 0..100 {
+                assert_eq!(extended.get(&i).unwrap(), Some(i * 3));
+            }
+        }
+
+        assert_from_iter_and_extend::<ConcurrentHashMap<_, _>>();
+        assert_from_iter_and_extend::<ConcurrentBTreeMap<_, _>>();
+        assert_from_iter_and_extend::<ShardedConcurrentHashMap<_, _>>();
+    }
+
+    #[test]
+    #[cfg(feature = "lru")]
+    fn test_lru_hash_map_extend() {
+        let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(10);
+        map.extend((0..20).map(|i| (i, i))).unwrap();
+
+        assert_eq!(map.len().unwrap(), 10);
+        assert!(!map.contains_key(&9).unwrap());
+        assert!(map.contains_key(&10).unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_maps_insert_returns_previous() {
+        fn assert_insert_returns_previous<M: Map<u32, &'static str>>() {
+            let map = M::new();
+
+            assert_eq!(map.insert(1, "first").unwrap(), None);
+            assert_eq!(map.insert(1, "second").unwrap(), Some("first"));
+            assert_eq!(map.get(&1).unwrap(), Some("second"));
+        }
+
+        assert_insert_returns_previous::<ConcurrentHashMap<_, _>>();
+        assert_insert_returns_previous::<ConcurrentBTreeMap<_, _>>();
+        assert_insert_returns_previous::<ShardedConcurrentHashMap<_, _>>();
+        #[cfg(feature = "lru")]
+        assert_insert_returns_previous::<LruHashMap<_, _>>();
+    }
+
+    #[test]
+    fn test_concurrent_maps_retain() {
+        fn assert_retain_works<M: Map<u32, u32>>() {
+            let map = M::new();
+            for i in 0..100 {
+                map.insert(i, i * 10).unwrap();
+            }
+
+            map.retain(|k, _| k % 2 == 0).unwrap();
+
+            assert_eq!(map.len().unwrap(), 50);
+            for i in 0..100 {
+                assert_eq!(map.contains_key(&i).unwrap(), i % 2 == 0);
+            }
+            assert_eq!(map.get(&42).unwrap(), Some(420));
+        }
+
+        assert_retain_works::<ConcurrentHashMap<_, _>>();
+        assert_retain_works::<ConcurrentBTreeMap<_, _>>();
+        assert_retain_works::<ShardedConcurrentHashMap<_, _>>();
+        #[cfg(feature = "lru")]
+        assert_retain_works::<LruHashMap<_, _>>();
+    }
+
+    #[test]
+    #[cfg(feature = "lru")]
+    fn test_lru_hash_map_retain_keeps_recency() {
+        let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(3);
+        map.insert(1, 1).unwrap();
+        map.insert(2, 2).unwrap();
+        map.insert(3, 3).unwrap();
+        map.get(&1).unwrap(); // 2 is now the least recently used
+
+        map.retain(|k, _| *k != 3).unwrap();
+        map.insert(4, 4).unwrap();
+        map.insert(5, 5).unwrap(); // evicts 2
+
+        assert!(!map.contains_key(&2).unwrap());
+        assert!(map.contains_key(&1).unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_maps_poison() {
+        fn assert_poison_propagates<M: Map<u32, u32> + Send + Sync>() -> M {
+            let map = M::new();
+            map.insert(1, 1).unwrap();
+
+            // panicking while `update` holds the lock poisons it
+            std::thread::scope(|s| {
+                let result = s
+                    .spawn(|| map.update(&1, |_| panic!("poisoning the lock")))
+                    .join();
+                assert!(result.is_err());
+            });
+
+            let poison = ConcurrentCollectionError::Poison;
+            assert_eq!(map.get(&1), Err(poison));
+            assert_eq!(map.remove(&1), Err(poison));
+            assert_eq!(map.len(), Err(poison));
+            assert_eq!(map.is_empty(), Err(poison));
+            assert_eq!(map.contains_key(&1), Err(poison));
+            assert_eq!(map.update(&1, |v| *v += 1), Err(poison));
+            assert_eq!(map.get_or_insert_with(2, || 2), Err(poison));
+            assert_eq!(map.insert(2, 2), Err(poison));
+            map
+        }
+
+        let poison = ConcurrentCollectionError::Poison;
+        let map = assert_poison_propagates::<ConcurrentHashMap<_, _>>();
+        assert_eq!(map.capacity(), Err(poison));
+        assert_poison_propagates::<ConcurrentBTreeMap<_, _>>();
+        #[cfg(feature = "lru")]
+        {
+            let map = assert_poison_propagates::<LruHashMap<_, _>>();
+            assert_eq!(map.capacity(), Err(poison));
+        }
+    }
+
+    #[test]
+    fn test_concurrent_hash_map_get_or_insert_with() {
+        assert_get_or_insert_with_works::<ConcurrentHashMap<_, _>>();
+    }
+
+    #[test]
+    fn test_concurrent_btree_map_get_or_insert_with() {
+        assert_get_or_insert_with_works::<ConcurrentBTreeMap<_, _>>();
+    }
+
+    #[test]
+    fn test_sharded_concurrent_hash_map_get_or_insert_with() {
+        assert_get_or_insert_with_works::<ShardedConcurrentHashMap<_, _>>();
+    }
+
+    #[test]
+    #[cfg(feature = "lru")]
+    fn test_lru_hash_map_get_or_insert_with() {
+        assert_get_or_insert_with_works::<LruHashMap<_, _>>();
+    }
+
+    #[test]
+    #[cfg(feature = "lru")]
+    fn test_lru_hash_map_eviction_order() {
+        let map: LruHashMap<u32, u32> = LruHashMap::with_capacity(3);
+
+        map.insert(1, 10).unwrap();
+        map.insert(2, 20).unwrap();
+        map.insert(3, 30).unwrap();
+
+        // 1 becomes the most recently used, so 2 is next in line
+        assert_eq!(map.get(&1).unwrap(), Some(10));
+
+        map.insert(4, 40).unwrap();
+        assert_eq!(map.len().unwrap(), 3);
+        assert!(!map.contains_key(&2).unwrap());
+
+        map.insert(5, 50).unwrap();
+        assert!(!map.contains_key(&3).unwrap());
+
+        assert_eq!(map.get(&1).unwrap(), Some(10));
+        assert_eq!(map.get(&4).unwrap(), Some(40));
+        assert_eq!(map.get(&5).unwrap(), Some(50));
+    }
+
+    #[test]
+    #[cfg(feature = "lru")]
+    fn test_lru_hash_map_concurrent() {
+        const CAPACITY: usize = 100;
+        const NUM_THREADS: usize = 10;
+        const OPS_PER_THREAD: usize = 1000;
+
+        let map: LruHashMap<usize, usize> = LruHashMap::with_capacity(CAPACITY);
+
+        (0..NUM_THREADS).into_par_iter().for_each(|thread_id| {
+            for i in 0..OPS_PER_THREAD {
+                let key = thread_id * OPS_PER_THREAD + i;
+                map.insert(key, key * 2).unwrap();
+                assert!(map.len().unwrap() <= CAPACITY);
+
+                // recently inserted keys may already be evicted by other threads,
+                // but whatever is returned must be the right value
+                if let Some(value) = map.get(&(key / 2)).unwrap() {
+                    assert_eq!(value, key / 2 * 2);
+                }
+            }
+        });
+
+        assert_eq!(map.len().unwrap(), CAPACITY);
+        assert_eq!(map.capacity().unwrap(), CAPACITY);
+    }
+
+    #[test]
+    #[cfg(feature = "lru")]
+    #[should_panic(expected = "Capacity must be greater than 0")]
+    fn test_lru_hash_map_zero_capacity() {
+        let _: LruHashMap<u32, u32> = LruHashMap::with_capacity(0);
+    }
+
+    /// Asserts that [`Map::get_or_insert_with`] only calls the closure once per missing key.
+    ///
+    /// This function is only used for testing.
+    fn assert_get_or_insert_with_works<M>()
+    where
+        M: Map<usize, usize> + Send + Sync,
+    {
+        const NUM_THREADS: usize = 10;
+        const OPS_PER_THREAD: usize = 1000;
+        const NUM_KEYS: usize = 10;
+
+        let map = M::new();
+        let calls = AtomicUsize::new(0);
+
+        (0..NUM_THREADS).into_par_iter().for_each(|_| {
+            for i in 0..OPS_PER_THREAD {
+                let key = i % NUM_KEYS;
+                let value = map
+                    .get_or_insert_with(key, || {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        key * 2
+                    })
+                    .unwrap();
+                assert_eq!(value, key * 2);
+            }
+        });
+
+        assert_eq!(calls.load(Ordering::SeqCst), NUM_KEYS);
+        assert_eq!(map.len().unwrap(), NUM_KEYS);
+    }
+
     /// Asserts that a map works as expected.
     ///
     /// This function is only used for testing.
@@ -404,13 +2217,13 @@ mod tests {
             }
         });
 
-        assert_eq!(map.len(), NUM_THREADS * OPS_PER_THREAD);
+        assert_eq!(map.len().unwrap(), NUM_THREADS * OPS_PER_THREAD);
 
         for thread_id in 0..NUM_THREADS {
             for i in 0..OPS_PER_THREAD {
                 let key = thread_id * OPS_PER_THREAD + i;
                 let value = (key * 2) as u32;
-                assert_eq!(map.get(&key), Some(value));
+                assert_eq!(map.get(&key).unwrap(), Some(value));
             }
         }
 
@@ -422,12 +2235,12 @@ mod tests {
                 let operation = rng.random_range(0..3);
 
                 match operation {
-                    0 => if let Some(_value) = map.get(&key) {},
+                    0 => if let Some(_value) = map.get(&key).unwrap() {},
                     1 => {
                         let value: u32 = rng.random();
                         map.insert(key, value).unwrap();
                     }
-                    2 => if let Some(_value) = map.remove(&key) {},
+                    2 => if let Some(_value) = map.remove(&key).unwrap() {},
                     _ => unreachable!(),
                 }
             }
@@ -441,7 +2254,7 @@ mod tests {
                 let operation = rng.random_range(0..2);
 
                 match operation {
-                    0 => if let Some(_value) = map.get(&key) {},
+                    0 => if let Some(_value) = map.get(&key).unwrap() {},
                     1 => {
                         let value: u32 = rng.random();
                         map.insert(key, value).unwrap();
@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Errors that can occur when using the concurrent collections from the collections module.
-#[derive(Debug, Error, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
 pub enum ConcurrentCollectionError {
     #[error("lock is poisoned")]
     Poison,
@@ -13,6 +13,16 @@ pub enum ConcurrentCollectionError {
 pub enum ParseError {
     #[error("parse error: invalid number: {0}")]
     InvalidNumber(String),
+
+    #[error("parse error: unknown unit: {0}")]
+    UnknownUnit(String),
+}
+
+/// Errors that can occur when working with the pattern module.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum PatternError {
+    #[error("invalid glob pattern `{0}`: {1}")]
+    InvalidGlob(String, String),
 }
 
 /// Errors that can occur when working with the [filesystem](`crate::fs`) module.
@@ -33,8 +43,23 @@ pub enum FsError {
     #[error("failed to get file type for `{0}`")]
     FileType(PathBuf),
 
+    #[error("failed to get metadata for `{0}`")]
+    Metadata(PathBuf),
+
     #[error("skipping non-file/non-directory entry: {0}")]
     NonFileNonDir(PathBuf),
+
+    #[error("destination is inside the source directory: {0}")]
+    DestinationInsideSource(PathBuf),
+
+    #[error("skipping symbolic link to an already walked directory: {0}")]
+    SymlinkLoop(PathBuf),
+
+    #[error("failed to parse gitignore file: {0}")]
+    Gitignore(PathBuf),
+
+    #[error(transparent)]
+    InvalidGlob(#[from] PatternError),
 }
 
 impl FsError {
@@ -70,6 +95,14 @@ impl FsError {
         Self::FileType(path.as_ref().to_path_buf())
     }
 
+    /// Create a new [`FsError::Metadata`]
+    pub fn metadata<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self::Metadata(path.as_ref().to_path_buf())
+    }
+
     /// Create a new [`FsError::NonFileNonDir`]
     pub fn non_file_non_dir<P>(path: P) -> Self
     where
@@ -77,4 +110,28 @@ impl FsError {
     {
         Self::NonFileNonDir(path.as_ref().to_path_buf())
     }
+
+    /// Create a new [`FsError::DestinationInsideSource`]
+    pub fn destination_inside_source<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self::DestinationInsideSource(path.as_ref().to_path_buf())
+    }
+
+    /// Create a new [`FsError::SymlinkLoop`]
+    pub fn symlink_loop<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self::SymlinkLoop(path.as_ref().to_path_buf())
+    }
+
+    /// Create a new [`FsError::Gitignore`]
+    pub fn gitignore<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self::Gitignore(path.as_ref().to_path_buf())
+    }
 }
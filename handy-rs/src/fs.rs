@@ -25,6 +25,17 @@
 //! }
 //! ```
 //!
+//! ### Copying directories
+//!
+//! [`copy_dir`] copies a directory recursively, reporting each copied file to a callback.
+//!
+//! ```rust,no_run
+//! use handy::fs::copy_dir;
+//!
+//! let bytes = copy_dir("/path/to/src", "/path/to/dst", |path| println!("{}", path.display())).unwrap();
+//! println!("copied {bytes} bytes");
+//! ```
+//!
 //! ## Benchmarks
 //!
 //! ```text
@@ -39,12 +50,23 @@
 //! ```
 
 use crate::errors::FsError;
+#[cfg(feature = "pattern")]
+use crate::{errors::PatternError, pattern::GlobMatcher};
 use anyhow::Result;
 use colored::Colorize;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "gitignore")]
+use ignore::{gitignore::Gitignore, Match};
+use rayon::iter::{IntoParallelIterator, ParallelBridge, ParallelIterator};
+#[cfg(feature = "gitignore")]
+use std::sync::Arc;
 use std::{
-    fs::{read_dir, DirEntry, ReadDir},
+    collections::{HashSet, VecDeque},
+    fs::{canonicalize, copy, create_dir_all, read_dir, DirEntry, ReadDir},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
 };
 
 /// A directory walker meant to be faster than alternatives like [`walkdir`](https://crates.io/crates/walkdir) and [`ignore`](https://crates.io/crates/ignore) but still close to [`std::fs::read_dir`], returning [`std::fs::DirEntry`] instead of a custom wrapper.
@@ -69,14 +91,67 @@ use std::{
 ///     println!("{}", entry.path().display());
 /// }
 /// ```
-#[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Walker {
     current: Option<ReadDir>,
-    to_walk: Vec<PathBuf>,
+    current_depth: usize,
+    current_ignores: Gitignores,
+    to_walk: VecDeque<(PathBuf, usize, Gitignores)>,
+    parents: Vec<(ReadDir, usize, Gitignores)>,
 
     path: PathBuf,
     colored: bool,
     print: bool,
+    max_depth: Option<usize>,
+    entry_kind: EntryKind,
+    #[cfg(feature = "pattern")]
+    glob: Option<std::result::Result<GlobMatcher, PatternError>>,
+    #[cfg(feature = "gitignore")]
+    respect_gitignore: bool,
+    filter: Option<EntryFilter>,
+    extensions: Option<HashSet<String>>,
+    follow_links: bool,
+    sorted: bool,
+    breadth_first: bool,
+    visited: HashSet<PathBuf>,
+}
+
+/// A predicate used by [`Walker::filter`]
+type EntryFilter = Box<dyn Fn(&DirEntry) -> bool + Send + Sync>;
+
+impl std::fmt::Debug for Walker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Walker");
+        debug
+            .field("current", &self.current)
+            .field("current_depth", &self.current_depth)
+            .field("current_ignores", &self.current_ignores)
+            .field("to_walk", &self.to_walk)
+            .field("parents", &self.parents)
+            .field("path", &self.path)
+            .field("colored", &self.colored)
+            .field("print", &self.print)
+            .field("max_depth", &self.max_depth)
+            .field("entry_kind", &self.entry_kind)
+            .field("extensions", &self.extensions)
+            .field("follow_links", &self.follow_links)
+            .field("sorted", &self.sorted)
+            .field("breadth_first", &self.breadth_first)
+            .field("visited", &self.visited);
+
+        #[cfg(feature = "pattern")]
+        debug.field("glob", &self.glob);
+
+        #[cfg(feature = "gitignore")]
+        debug.field("respect_gitignore", &self.respect_gitignore);
+
+        debug
+            .field(
+                "filter",
+                &self.filter.as_ref().map(|_| "Fn(&DirEntry) -> bool"),
+            )
+            .finish()
+    }
 }
 
 impl Walker {
@@ -100,10 +175,25 @@ impl Walker {
         let path = path.as_ref();
         Self {
             current: None,
-            to_walk: Vec::new(),
+            current_depth: 1,
+            current_ignores: Gitignores::default(),
+            to_walk: VecDeque::new(),
+            parents: Vec::new(),
             path: path.to_path_buf(),
             colored: false,
             print: false,
+            max_depth: None,
+            entry_kind: EntryKind::All,
+            #[cfg(feature = "pattern")]
+            glob: None,
+            #[cfg(feature = "gitignore")]
+            respect_gitignore: false,
+            filter: None,
+            extensions: None,
+            follow_links: false,
+            sorted: false,
+            breadth_first: true,
+            visited: HashSet::new(),
         }
     }
 
@@ -149,6 +239,339 @@ impl Walker {
         self
     }
 
+    /// Set the maximum depth to walk, the immediate children of the path are at depth `1` so with `max_depth(1)` only
+    /// those are returned and no directory is descended into. A depth of `0` returns no entries.
+    ///
+    /// Default: no limit
+    ///
+    /// ## Arguments
+    ///
+    /// * `depth` - The maximum depth of the entries
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let walker = Walker::new("/path/to/dir").max_depth(2);
+    /// ```
+    #[must_use]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Only return the entries whose filename matches a glob pattern, directories are still walked even if they don't match.
+    ///
+    /// Requires the `pattern` feature.
+    ///
+    /// Default: all entries are returned
+    ///
+    /// ## Arguments
+    ///
+    /// * `pattern` - The glob pattern, see [`GlobMatcher`], an invalid pattern makes walking return
+    ///   [`FsError::InvalidGlob`]
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let walker = Walker::new("/path/to/dir").glob("*.txt");
+    /// ```
+    #[cfg(feature = "pattern")]
+    #[must_use]
+    pub fn glob(mut self, pattern: &str) -> Self {
+        self.glob = Some(GlobMatcher::new(pattern));
+        self
+    }
+
+    /// Skip the entries matched by the `.gitignore` files found while walking, a `.gitignore` applies to the directory
+    /// it's in and all of its subdirectories, with the deeper ones taking precedence. Ignored directories are not walked.
+    ///
+    /// Requires the `gitignore` feature.
+    ///
+    /// Default: `false`
+    ///
+    /// ## Arguments
+    ///
+    /// * `respect_gitignore` - Whether or not to skip the ignored entries
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let walker = Walker::new("/path/to/dir").respect_gitignore(true);
+    /// ```
+    #[cfg(feature = "gitignore")]
+    #[must_use]
+    pub fn respect_gitignore(mut self, respect_gitignore: bool) -> Self {
+        self.respect_gitignore = respect_gitignore;
+        self
+    }
+
+    /// Only return the files whose extension is one of `extensions`, ignoring case. Directories are always returned and
+    /// walked, use [`Walker::files_only`] to leave them out.
+    ///
+    /// Default: all files are returned
+    ///
+    /// ## Arguments
+    ///
+    /// * `extensions` - The allowed extensions, without the leading dot
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let walker = Walker::new("/path/to/dir").extensions(&["rs", "toml"]);
+    /// ```
+    #[must_use]
+    pub fn extensions(mut self, extensions: &[&str]) -> Self {
+        self.extensions = Some(extensions.iter().map(|e| e.to_lowercase()).collect());
+        self
+    }
+
+    /// Only return files, directories are still walked. Overrides [`Walker::dirs_only`].
+    ///
+    /// Default: `false`
+    ///
+    /// ## Arguments
+    ///
+    /// * `files_only` - Whether to only return files
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let walker = Walker::new("/path/to/dir").files_only(true);
+    /// ```
+    #[must_use]
+    pub fn files_only(mut self, files_only: bool) -> Self {
+        if files_only {
+            self.entry_kind = EntryKind::Files;
+        } else if self.entry_kind == EntryKind::Files {
+            self.entry_kind = EntryKind::All;
+        }
+        self
+    }
+
+    /// Only return directories. Overrides [`Walker::files_only`].
+    ///
+    /// Default: `false`
+    ///
+    /// ## Arguments
+    ///
+    /// * `dirs_only` - Whether to only return directories
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let walker = Walker::new("/path/to/dir").dirs_only(true);
+    /// ```
+    #[must_use]
+    pub fn dirs_only(mut self, dirs_only: bool) -> Self {
+        if dirs_only {
+            self.entry_kind = EntryKind::Dirs;
+        } else if self.entry_kind == EntryKind::Dirs {
+            self.entry_kind = EntryKind::All;
+        }
+        self
+    }
+
+    /// Set whether or not to walk into symbolic links to directories, the links are returned either way. Directories
+    /// that were already walked are not walked again, so a link pointing to one of its parents doesn't loop forever.
+    ///
+    /// Default: `false`
+    ///
+    /// ## Arguments
+    ///
+    /// * `follow_links` - Whether or not to follow symbolic links
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let walker = Walker::new("/path/to/dir").follow_links(true);
+    /// ```
+    #[must_use]
+    pub fn follow_links(mut self, follow_links: bool) -> Self {
+        self.follow_links = follow_links;
+        self
+    }
+
+    /// Set whether or not to sort the entries returned by [`Walker::par_walk`] by path, otherwise their order depends
+    /// on how the work was split between threads.
+    ///
+    /// Default: `false`
+    ///
+    /// ## Arguments
+    ///
+    /// * `sorted` - Whether or not to sort the entries
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let entries = Walker::new("/path/to/dir").sorted(true).par_walk().unwrap();
+    /// ```
+    #[must_use]
+    pub fn sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Set the order in which [`Walker::walk`] returns the entries. Breadth-first returns all the entries of a directory
+    /// before the ones of its subdirectories, while depth-first returns the entries of a directory right after the
+    /// directory itself. It has no effect on [`Walker::par_walk`].
+    ///
+    /// Default: `true`
+    ///
+    /// ## Arguments
+    ///
+    /// * `breadth_first` - Whether to walk breadth-first instead of depth-first
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// // print a tree of the directory
+    /// for entry in Walker::new("/path/to/dir").breadth_first(false).walk_with_depth().unwrap() {
+    ///     let (entry, depth) = entry.unwrap();
+    ///     println!("{}{}", "  ".repeat(depth), entry.file_name().to_string_lossy());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn breadth_first(mut self, breadth_first: bool) -> Self {
+        self.breadth_first = breadth_first;
+        self
+    }
+
+    /// Whether a directory should be walked into, checking for loops if [`Walker::follow_links`] is enabled
+    ///
+    /// ## Arguments
+    ///
+    /// * `path` - The path of the directory
+    /// * `is_link` - Whether the directory is a symbolic link
+    /// * `visited` - The canonical paths of the directories walked so far
+    ///
+    /// ## Errors
+    ///
+    /// - [`FsError::DirRead`]: The directory could not be resolved
+    /// - [`FsError::SymlinkLoop`]: The directory was already walked
+    fn should_descend(
+        &self,
+        path: &Path,
+        is_link: bool,
+        visited: &mut HashSet<PathBuf>,
+    ) -> std::result::Result<bool, FsError> {
+        if !self.follow_links {
+            return Ok(!is_link);
+        }
+
+        let canonical = canonicalize(path).map_err(|_| FsError::dir_read(path))?;
+        if visited.insert(canonical) {
+            Ok(true)
+        } else {
+            Err(FsError::symlink_loop(path))
+        }
+    }
+
+    /// Only return the entries for which the predicate returns `true`, directories are still walked even if they're not returned.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - The predicate
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// // only files bigger than 1MB
+    /// let walker = Walker::new("/path/to/dir").filter(|e| e.metadata().is_ok_and(|m| m.is_file() && m.len() > 1_048_576));
+    /// ```
+    #[must_use]
+    pub fn filter<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&DirEntry) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Box::new(f));
+        self
+    }
+
+    /// Whether an entry should be returned according to [`Walker::files_only`], [`Walker::dirs_only`], [`Walker::glob`] and [`Walker::filter`]
+    fn should_yield(&self, entry: &DirEntry, path: &Path, is_dir: bool) -> bool {
+        match self.entry_kind {
+            EntryKind::Files if is_dir => return false,
+            EntryKind::Dirs if !is_dir => return false,
+            _ => (),
+        }
+
+        if let Some(filter) = &self.filter {
+            if !filter(entry) {
+                return false;
+            }
+        }
+
+        if let Some(extensions) = &self.extensions {
+            if !is_dir {
+                let allowed = path
+                    .extension()
+                    .is_some_and(|e| extensions.contains(&e.to_string_lossy().to_lowercase()));
+                if !allowed {
+                    return false;
+                }
+            }
+        }
+
+        #[cfg(feature = "pattern")]
+        if let Some(Ok(matcher)) = &self.glob {
+            return matcher.is_match(path);
+        }
+
+        #[cfg(not(feature = "pattern"))]
+        let _ = path;
+
+        true
+    }
+
+    /// Whether entries at the given depth are within [`Walker::max_depth`]
+    fn within_depth(&self, depth: usize) -> bool {
+        self.max_depth.map_or(true, |max| depth <= max)
+    }
+
+    /// Start reading a directory found by the iterator, `depth` is the depth of its entries and `ignores` are the
+    /// gitignores of its parent
+    fn open_dir(
+        &mut self,
+        (path, depth, ignores): (PathBuf, usize, Gitignores),
+    ) -> std::io::Result<()> {
+        self.current = Some(read_dir(&path)?);
+        self.current_depth = depth;
+        self.current_ignores = ignores.enter(self, &path, |e| self.eprintln(&e));
+        Ok(())
+    }
+
+    /// Print an error message and add it to the errors of a parallel walk if they're being collected
+    fn report(&self, err: FsError, state: &ParState) {
+        self.eprintln(&err);
+
+        if let Some(errors) = &state.errors {
+            errors
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(err);
+        }
+    }
+
     /// Print an error message
     fn eprintln(&self, err: &FsError) {
         if self.print {
@@ -180,10 +603,49 @@ impl Walker {
     /// }
     /// ```
     pub fn walk(mut self) -> std::io::Result<Self> {
+        #[cfg(feature = "pattern")]
+        if let Some(Err(err)) = &self.glob {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                FsError::from(err.clone()),
+            ));
+        }
+
         self.current = Some(read_dir(&self.path)?);
+        self.current_ignores =
+            Gitignores::default().enter(&self, &self.path, |e| self.eprintln(&e));
+
+        if self.follow_links {
+            self.visited.insert(canonicalize(&self.path)?);
+        }
+
         Ok(self)
     }
 
+    /// Start walking the directory, returning the depth of each entry along with it like [`Walker::par_walk_with_depth`]
+    ///
+    /// ## Returns
+    ///
+    /// Returns a [`DepthWalker`] which can be used as an iterator
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the path does not exist or if the entries could not be read
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// for entry in Walker::new("/path/to/dir").walk_with_depth().unwrap() {
+    ///     let (entry, depth) = entry.unwrap();
+    ///     println!("{}{}", "  ".repeat(depth), entry.file_name().to_string_lossy());
+    /// }
+    /// ```
+    pub fn walk_with_depth(self) -> std::io::Result<DepthWalker> {
+        Ok(DepthWalker(self.walk()?))
+    }
+
     /// Start walking the directory in parallel
     ///
     /// ## Returns
@@ -204,58 +666,364 @@ impl Walker {
     /// }
     /// ```
     pub fn par_walk(&self) -> Result<Vec<DirEntry>> {
-        let path = &self.path;
-
-        if !path.exists() {
-            return Err(FsError::path_does_not_exist(path).into());
-        }
+        Ok(self
+            .par_walk_with_depth()?
+            .into_iter()
+            .map(|(entry, _)| entry)
+            .collect())
+    }
 
-        if !path.is_dir() {
-            return Err(FsError::path_is_not_directory(path).into());
+    /// Start walking the directory in parallel, returning the depth of each entry along with it. The immediate children
+    /// of the path are at depth `0`, which is one less than the depth used by [`Walker::max_depth`], so it can be used
+    /// directly as the indentation level.
+    ///
+    /// ## Returns
+    ///
+    /// Returns a vector of [`DirEntry`] and their depth
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the path does not exist or if the entries could not be read
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// for (entry, depth) in Walker::new("/path/to/dir").sorted(true).par_walk_with_depth().unwrap() {
+    ///     println!("{}{}", "  ".repeat(depth), entry.file_name().to_string_lossy());
+    /// }
+    /// ```
+    pub fn par_walk_with_depth(&self) -> Result<Vec<(DirEntry, usize)>> {
+        let state = self.par_state(false)?;
+        let mut entries = self.par_walk_inner(&self.path, 1, &state, &Gitignores::default())?;
+        if self.sorted {
+            entries.sort_by_cached_key(|(entry, _)| entry.path());
         }
 
-        self.par_walk_inner(path)
+        Ok(entries)
     }
 
-    /// Start walking the directory in parallel
-    fn par_walk_inner<P>(&self, path: P) -> Result<Vec<DirEntry>>
-    where
-        P: AsRef<Path>,
-    {
+    /// Start walking the directory in parallel like [`Walker::par_walk`], but instead of only printing the errors
+    /// found along the way (like directories that couldn't be read) they're returned with the entries.
+    ///
+    /// ## Returns
+    ///
+    /// A vector of [`DirEntry`] and a vector of the errors found, if the path itself can't be walked there are no
+    /// entries and the errors contain the reason
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let (entries, errors) = Walker::new("/path/to/dir").par_walk_collect_errors();
+    /// for err in errors {
+    ///     eprintln!("{err}");
+    /// }
+    /// ```
+    pub fn par_walk_collect_errors(&self) -> (Vec<DirEntry>, Vec<FsError>) {
+        let state = match self.par_state(true) {
+            Ok(state) => state,
+            Err(err) => return (vec![], vec![err]),
+        };
+
+        let mut entries: Vec<DirEntry> = self
+            .par_walk_inner(&self.path, 1, &state, &Gitignores::default())
+            .map_or_else(
+                |_| {
+                    self.report(FsError::dir_read(&self.path), &state);
+                    vec![]
+                },
+                |entries| entries.into_iter().map(|(entry, _)| entry).collect(),
+            );
+        if self.sorted {
+            entries.sort_by_cached_key(DirEntry::path);
+        }
+
+        let errors = state
+            .errors
+            .map(|errors| errors.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .unwrap_or_default();
+        (entries, errors)
+    }
+
+    /// Checks that the path can be walked and creates the state of a parallel walk
+    ///
+    /// ## Arguments
+    ///
+    /// * `collect_errors` - Whether to collect the errors found while walking
+    ///
+    /// ## Errors
+    ///
+    /// - [`FsError::InvalidGlob`]: The pattern given to [`Walker::glob`] is invalid
+    /// - [`FsError::PathDoesNotExist`]: The path does not exist
+    /// - [`FsError::PathIsNotDirectory`]: The path is not a directory
+    /// - [`FsError::DirRead`]: The path could not be resolved, only if [`Walker::follow_links`] is enabled
+    fn par_state(&self, collect_errors: bool) -> std::result::Result<ParState, FsError> {
+        #[cfg(feature = "pattern")]
+        if let Some(Err(err)) = &self.glob {
+            return Err(err.clone().into());
+        }
+
+        let path = &self.path;
+
+        if !path.exists() {
+            return Err(FsError::path_does_not_exist(path));
+        }
+
+        if !path.is_dir() {
+            return Err(FsError::path_is_not_directory(path));
+        }
+
+        let mut visited = HashSet::new();
+        if self.follow_links {
+            visited.insert(canonicalize(path).map_err(|_| FsError::dir_read(path))?);
+        }
+
+        Ok(ParState {
+            visited: Mutex::new(visited),
+            errors: collect_errors.then(|| Mutex::new(Vec::new())),
+        })
+    }
+
+    /// Sums the size in bytes of all regular files in the directory in parallel, entries not returned by the walker
+    /// (because of [`Walker::max_depth`], [`Walker::filter`] and so on) are not counted.
+    ///
+    /// ## Returns
+    ///
+    /// The total size in bytes
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the path does not exist or if the entries could not be read
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let size = Walker::new("/path/to/dir").total_size().unwrap();
+    /// println!("{size} bytes");
+    /// ```
+    pub fn total_size(&self) -> Result<u64> {
+        Ok(self
+            .par_walk()?
+            .into_par_iter()
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+            .map(|e| {
+                e.metadata().map_or_else(
+                    |_| {
+                        self.eprintln(&FsError::metadata(e.path()));
+                        0
+                    },
+                    |m| m.len(),
+                )
+            })
+            .sum())
+    }
+
+    /// Start walking the directory in parallel, calling `f` for each entry as soon as it's found instead of collecting
+    /// them first, the order of the entries is not guaranteed.
+    ///
+    /// ## Arguments
+    ///
+    /// * `f` - The function to call for each entry
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the path does not exist or if the entries could not be read
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// Walker::new("/path/to/dir")
+    ///     .par_walk_for_each(|entry| println!("{}", entry.path().display()))
+    ///     .unwrap();
+    /// ```
+    pub fn par_walk_for_each<F>(&self, f: F) -> Result<()>
+    where
+        F: Fn(DirEntry) + Sync,
+    {
+        let state = self.par_state(false)?;
+        self.par_walk_for_each_inner(&self.path, 1, &state, &Gitignores::default(), &f);
+        Ok(())
+    }
+
+    /// Counts the entries in the directory in parallel without collecting them, entries not returned by the walker
+    /// (because of [`Walker::files_only`], [`Walker::filter`] and so on) are not counted.
+    ///
+    /// Named `par_count` so it doesn't clash with [`Iterator::count`] on the walker.
+    ///
+    /// ## Returns
+    ///
+    /// The number of entries
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the path does not exist or if the entries could not be read
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::fs::Walker;
+    ///
+    /// let files = Walker::new("/path/to/dir").files_only(true).par_count().unwrap();
+    /// ```
+    pub fn par_count(&self) -> Result<usize> {
+        let count = AtomicUsize::new(0);
+        self.par_walk_for_each(|_| {
+            count.fetch_add(1, Ordering::Relaxed);
+        })?;
+
+        Ok(count.into_inner())
+    }
+
+    /// Walk the directory in parallel calling `f` for each entry, `depth` is the depth of the entries in `path` and
+    /// `ignores` are the gitignores of its parent
+    fn par_walk_for_each_inner<F>(
+        &self,
+        path: &Path,
+        depth: usize,
+        state: &ParState,
+        ignores: &Gitignores,
+        f: &F,
+    ) where
+        F: Fn(DirEntry) + Sync,
+    {
+        if !self.within_depth(depth) {
+            return;
+        }
+
+        let Ok(entries) = read_dir(path) else {
+            self.report(FsError::dir_read(path), state);
+            return;
+        };
+
+        let ignores = ignores.enter(self, path, |e| self.report(e, state));
+        entries.par_bridge().for_each(|e| {
+            let Ok(e) = e else {
+                self.report(FsError::DirEntry, state);
+                return;
+            };
+
+            let Some((should_yield, descend)) = self.visit_entry(&e, depth, state, &ignores) else {
+                return;
+            };
+
+            let entry_path = e.path();
+            if should_yield {
+                f(e);
+            }
+
+            if descend {
+                self.par_walk_for_each_inner(&entry_path, depth + 1, state, &ignores, f);
+            }
+        });
+    }
+
+    /// Checks an entry found while walking in parallel, `depth` is the depth of the entry and `ignores` are the
+    /// gitignores of the directory it's in
+    ///
+    /// ## Returns
+    ///
+    /// Whether the entry should be returned and whether it should be walked into, or `None` if the entry is skipped
+    fn visit_entry(
+        &self,
+        e: &DirEntry,
+        depth: usize,
+        state: &ParState,
+        ignores: &Gitignores,
+    ) -> Option<(bool, bool)> {
+        let entry_path = e.path();
+        let Ok(file_type) = e.file_type() else {
+            self.report(FsError::FileType(entry_path), state);
+            return None;
+        };
+
+        let is_link = file_type.is_symlink();
+        let is_dir = if is_link {
+            entry_path.is_dir()
+        } else {
+            file_type.is_dir()
+        };
+
+        if ignores.is_ignored(&entry_path, is_dir) {
+            return None;
+        }
+
+        if is_dir {
+            let descend = self.within_depth(depth + 1) && {
+                let mut visited = state.visited.lock().unwrap_or_else(PoisonError::into_inner);
+                self.should_descend(&entry_path, is_link, &mut visited)
+                    .unwrap_or_else(|err| {
+                        self.report(err, state);
+                        false
+                    })
+            };
+
+            Some((self.should_yield(e, &entry_path, true), descend))
+        } else if file_type.is_file() || is_link {
+            Some((self.should_yield(e, &entry_path, false), false))
+        } else {
+            self.report(FsError::NonFileNonDir(entry_path), state);
+            None
+        }
+    }
+
+    /// Start walking the directory in parallel, `depth` is the depth of the entries in `path` and each entry is returned
+    /// with `depth - 1`, `ignores` are the gitignores of the parent of `path`
+    fn par_walk_inner<P>(
+        &self,
+        path: P,
+        depth: usize,
+        state: &ParState,
+        ignores: &Gitignores,
+    ) -> Result<Vec<(DirEntry, usize)>>
+    where
+        P: AsRef<Path>,
+    {
+        if !self.within_depth(depth) {
+            return Ok(vec![]);
+        }
+
         let path = path.as_ref();
         let entries: Vec<DirEntry> = if let Ok(entries) = read_dir(path) {
             entries
                 .filter_map(|e| {
                     e.inspect_err(|_| {
-                        self.eprintln(&FsError::DirEntry);
+                        self.report(FsError::DirEntry, state);
                     })
                     .ok()
                 })
                 .collect()
         } else {
-            self.eprintln(&FsError::dir_read(path));
+            self.report(FsError::dir_read(path), state);
             return Ok(vec![]);
         };
 
-        let results: Vec<Result<Vec<DirEntry>>> = entries
+        let ignores = ignores.enter(self, path, |e| self.report(e, state));
+        let results: Vec<Result<Vec<(DirEntry, usize)>>> = entries
             .into_par_iter()
             .map(|e| {
-                let entry_path = e.path();
-                let Ok(file_type) = e.file_type() else {
-                    self.eprintln(&FsError::FileType(entry_path));
+                let Some((should_yield, descend)) = self.visit_entry(&e, depth, state, &ignores)
+                else {
                     return Ok(vec![]);
                 };
 
-                if file_type.is_file() {
-                    Ok(vec![e])
-                } else if file_type.is_dir() {
-                    let mut entries = vec![e];
-                    entries.extend(self.par_walk_inner(entry_path)?);
-                    Ok(entries)
+                let entry_path = e.path();
+                let mut entries = if should_yield {
+                    vec![(e, depth - 1)]
                 } else {
-                    self.eprintln(&FsError::NonFileNonDir(entry_path));
-                    Ok(vec![])
+                    vec![]
+                };
+
+                if descend {
+                    entries.extend(self.par_walk_inner(entry_path, depth + 1, state, &ignores)?);
                 }
+                Ok(entries)
             })
             .collect();
 
@@ -268,19 +1036,77 @@ impl Walker {
     }
 }
 
+/// The kind of entries a [Walker] returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    /// Files and directories
+    All,
+
+    /// Only files
+    Files,
+
+    /// Only directories
+    Dirs,
+}
+
 impl Iterator for Walker {
     type Item = std::io::Result<DirEntry>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
+            // in depth-first order the directory that was just returned is read before the rest of its parent
+            if !self.breadth_first {
+                if let Some(next_dir) = self.to_walk.pop_back() {
+                    if let Some(parent) = self.current.take() {
+                        let ignores = std::mem::take(&mut self.current_ignores);
+                        self.parents.push((parent, self.current_depth, ignores));
+                    }
+
+                    if let Err(e) = self.open_dir(next_dir) {
+                        return Some(Err(e));
+                    }
+                }
+            }
+
+            if !self.within_depth(self.current_depth) {
+                self.current = None;
+            }
+
             if let Some(ref mut current_iter) = self.current {
                 match current_iter.next() {
                     Some(Ok(entry)) => {
                         let path = entry.path();
-                        if path.is_dir() {
-                            self.to_walk.push(path);
+                        let is_dir = path.is_dir();
+                        let is_link = entry.file_type().is_ok_and(|t| t.is_symlink());
+                        if self.current_ignores.is_ignored(&path, is_dir) {
+                            continue;
+                        }
+
+                        let should_yield = self.should_yield(&entry, &path, is_dir);
+
+                        if is_dir && self.within_depth(self.current_depth + 1) {
+                            let mut visited = std::mem::take(&mut self.visited);
+                            let descend = self
+                                .should_descend(&path, is_link, &mut visited)
+                                .unwrap_or_else(|err| {
+                                    self.eprintln(&err);
+                                    false
+                                });
+                            if descend {
+                                self.to_walk.push_back((
+                                    path,
+                                    self.current_depth + 1,
+                                    self.current_ignores.clone(),
+                                ));
+                            }
+                            self.visited = visited;
                         }
-                        return Some(Ok(entry));
+
+                        if should_yield {
+                            return Some(Ok(entry));
+                        }
+
+                        continue;
                     }
                     Some(Err(e)) => {
                         return Some(Err(e));
@@ -291,26 +1117,198 @@ impl Iterator for Walker {
                 }
             }
 
-            if let Some(next_dir_path) = self.to_walk.pop() {
-                match read_dir(next_dir_path) {
-                    Ok(new_iter) => {
-                        self.current = Some(new_iter);
-                    }
-                    Err(e) => {
-                        return Some(Err(e));
-                    }
+            if !self.breadth_first {
+                let (parent, depth, ignores) = self.parents.pop()?;
+                self.current = Some(parent);
+                self.current_depth = depth;
+                self.current_ignores = ignores;
+                continue;
+            }
+
+            let next_dir = self.to_walk.pop_front()?;
+            if let Err(e) = self.open_dir(next_dir) {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// The state shared by the threads of a parallel walk
+struct ParState {
+    /// The canonical paths of the directories walked so far, only used if [`Walker::follow_links`] is enabled
+    visited: Mutex<HashSet<PathBuf>>,
+
+    /// The errors found so far, if they're being collected by [`Walker::par_walk_collect_errors`]
+    errors: Option<Mutex<Vec<FsError>>>,
+}
+
+/// The `.gitignore` files that apply to a directory, from the root of the walk to the directory itself, used by
+/// [`Walker::respect_gitignore`]
+#[derive(Debug, Clone, Default)]
+struct Gitignores {
+    #[cfg(feature = "gitignore")]
+    ignores: Vec<Arc<Gitignore>>,
+}
+
+impl Gitignores {
+    /// Returns the gitignores that apply to `dir`, which is a subdirectory of the one these apply to, reading its
+    /// `.gitignore` if [`Walker::respect_gitignore`] is enabled. A `.gitignore` that can't be parsed is passed to
+    /// `report`.
+    fn enter<F>(&self, walker: &Walker, dir: &Path, report: F) -> Self
+    where
+        F: Fn(FsError),
+    {
+        #[cfg(feature = "gitignore")]
+        if walker.respect_gitignore {
+            let file = dir.join(".gitignore");
+            if file.is_file() {
+                let (gitignore, err) = Gitignore::new(&file);
+                if err.is_some() {
+                    report(FsError::gitignore(&file));
                 }
-            } else {
-                return None;
+
+                let mut ignores = self.ignores.clone();
+                ignores.push(Arc::new(gitignore));
+                return Self { ignores };
+            }
+        }
+
+        #[cfg(not(feature = "gitignore"))]
+        let _ = (walker, dir, report);
+
+        self.clone()
+    }
+
+    /// Whether an entry is ignored, the deepest `.gitignore` with a matching pattern decides
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        #[cfg(feature = "gitignore")]
+        for gitignore in self.ignores.iter().rev() {
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => (),
+            }
+        }
+
+        #[cfg(not(feature = "gitignore"))]
+        let _ = (self, path, is_dir);
+
+        false
+    }
+}
+
+/// A [Walker] that returns the depth of each entry along with it, created with [`Walker::walk_with_depth`]
+#[derive(Debug)]
+pub struct DepthWalker(Walker);
+
+impl Iterator for DepthWalker {
+    type Item = std::io::Result<(DirEntry, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.0.next()?;
+
+        // the entries are always from the directory being read, which is at `current_depth`
+        Some(entry.map(|entry| (entry, self.0.current_depth - 1)))
+    }
+}
+
+/// Copies a directory recursively, recreating the directory structure of `src` under `dst` and creating any missing
+/// parent directories. Symbolic links are skipped.
+///
+/// ## Arguments
+///
+/// * `src` - The directory to copy
+/// * `dst` - The directory to copy to, it's created if it doesn't exist
+/// * `on_progress` - Called with the path of each file in `src` after it's copied
+///
+/// ## Returns
+///
+/// The total number of bytes copied
+///
+/// ## Errors
+///
+/// - [`FsError::PathDoesNotExist`]: `src` does not exist
+/// - [`FsError::PathIsNotDirectory`]: `src` is not a directory
+/// - [`FsError::DestinationInsideSource`]: `dst` is inside `src`, which would never finish copying
+/// - Any error from reading the entries, creating the directories or copying the files
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use handy::fs::copy_dir;
+///
+/// let bytes = copy_dir("/path/to/src", "/path/to/dst", |path| println!("{}", path.display())).unwrap();
+/// ```
+pub fn copy_dir<S, D, F>(src: S, dst: D, on_progress: F) -> Result<u64>
+where
+    S: AsRef<Path>,
+    D: AsRef<Path>,
+    F: Fn(&Path),
+{
+    let (src, dst) = (src.as_ref(), dst.as_ref());
+
+    if !src.exists() {
+        return Err(FsError::path_does_not_exist(src).into());
+    }
+
+    if !src.is_dir() {
+        return Err(FsError::path_is_not_directory(src).into());
+    }
+
+    if resolve_path(dst)?.starts_with(canonicalize(src)?) {
+        return Err(FsError::destination_inside_source(dst).into());
+    }
+
+    create_dir_all(dst)?;
+
+    let mut total = 0;
+
+    for entry in Walker::new(src).walk()? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        let target = dst.join(path.strip_prefix(src)?);
+
+        if file_type.is_dir() {
+            create_dir_all(&target)?;
+        } else if file_type.is_file() {
+            if let Some(parent) = target.parent() {
+                create_dir_all(parent)?;
             }
+
+            total += copy(&path, &target)?;
+            on_progress(&path);
+        }
+    }
+
+    Ok(total)
+}
+
+/// Resolves a path that may not exist yet by canonicalizing its closest existing ancestor
+///
+/// ## Arguments
+///
+/// * `path` - The path to resolve
+fn resolve_path(path: &Path) -> Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    for ancestor in absolute.ancestors() {
+        if ancestor.exists() {
+            return Ok(canonicalize(ancestor)?.join(absolute.strip_prefix(ancestor)?));
         }
     }
+
+    Ok(absolute)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::helper::TempdirSetupBuilder;
+    use crate::helper::{pad_content, TempdirSetupBuilder};
 
     #[test]
     fn test_walker_iter() {
@@ -333,4 +1331,689 @@ mod tests {
             .expect("Failed to create walker");
         assert_eq!(dbg!(entries).len(), setup.entries_count());
     }
+
+    #[test]
+    fn test_walker_par_walk_for_each() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+        let walker = Walker::new(setup.path());
+
+        let count = AtomicUsize::new(0);
+        walker
+            .par_walk_for_each(|_| {
+                count.fetch_add(1, Ordering::Relaxed);
+            })
+            .expect("Failed to walk directories");
+
+        let entries = walker.par_walk().expect("Failed to walk directories");
+        assert_eq!(count.into_inner(), entries.len());
+    }
+
+    #[test]
+    fn test_walker_total_size() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+        let files = setup.files_in_root + setup.dir_count * setup.files_per_subdir;
+
+        let size = Walker::new(setup.path())
+            .total_size()
+            .expect("Failed to get total size");
+        assert_eq!(size, files as u64 * setup.file_size);
+
+        let size = Walker::new(setup.path())
+            .dirs_only(true)
+            .total_size()
+            .expect("Failed to get total size");
+        assert_eq!(size, 0);
+    }
+
+    #[test]
+    fn test_walker_par_count() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+
+        let walker = Walker::new(setup.path());
+        let count = walker.par_count().expect("Failed to count entries");
+        assert_eq!(
+            count,
+            walker.par_walk().expect("Failed to walk directories").len()
+        );
+        assert_eq!(count, setup.entries_count());
+
+        let walker = Walker::new(setup.path()).files_only(true);
+        let count = walker.par_count().expect("Failed to count entries");
+        assert_eq!(
+            count,
+            walker.par_walk().expect("Failed to walk directories").len()
+        );
+        assert_eq!(
+            count,
+            setup.files_in_root + setup.dir_count * setup.files_per_subdir
+        );
+    }
+
+    #[test]
+    fn test_walker_sorted() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+        let walker = Walker::new(setup.path()).sorted(true);
+
+        let first: Vec<PathBuf> = walker
+            .par_walk()
+            .expect("Failed to walk directories")
+            .iter()
+            .map(DirEntry::path)
+            .collect();
+        let second: Vec<PathBuf> = walker
+            .par_walk()
+            .expect("Failed to walk directories")
+            .iter()
+            .map(DirEntry::path)
+            .collect();
+
+        assert_eq!(first.len(), setup.entries_count());
+        assert!(first.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_walker_breadth_first() {
+        let root = tempfile::tempdir().expect("Failed to create tempdir");
+        let nested = root.path().join("a").join("b");
+        create_dir_all(&nested).expect("Failed to create directory");
+        create_dir_all(root.path().join("c")).expect("Failed to create directory");
+        for file in [
+            root.path().join("root.txt"),
+            root.path().join("a").join("a.txt"),
+            nested.join("b.txt"),
+            root.path().join("c").join("c.txt"),
+        ] {
+            std::fs::write(file, "content").expect("Failed to write file");
+        }
+
+        let walk = |breadth_first: bool| -> Vec<(PathBuf, usize)> {
+            Walker::new(root.path())
+                .breadth_first(breadth_first)
+                .walk_with_depth()
+                .expect("Failed to create walker")
+                .map(|e| {
+                    let (entry, depth) = e.expect("Failed to read entry");
+                    (entry.path(), depth)
+                })
+                .collect()
+        };
+        let position = |entries: &[(PathBuf, usize)], path: &Path| {
+            entries
+                .iter()
+                .position(|(p, _)| p == path)
+                .expect("Entry not found")
+        };
+
+        let bfs = walk(true);
+        assert_eq!(bfs.len(), 7);
+        assert!(bfs.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(
+            position(&bfs, &root.path().join("c"))
+                < position(&bfs, &root.path().join("a").join("a.txt"))
+        );
+
+        let dfs = walk(false);
+        assert_eq!(dfs.len(), 7);
+        for dir in [root.path().join("a"), nested.clone(), root.path().join("c")] {
+            let dir_position = position(&dfs, &dir);
+            let children: Vec<&(PathBuf, usize)> = dfs[dir_position + 1..]
+                .iter()
+                .take_while(|(p, _)| p.starts_with(&dir))
+                .collect();
+            let expected = if dir == root.path().join("a") { 3 } else { 1 };
+            assert_eq!(
+                children.len(),
+                expected,
+                "{} children are not right after it",
+                dir.display()
+            );
+        }
+        assert_eq!(
+            position(&dfs, &nested.join("b.txt")),
+            position(&dfs, &nested) + 1
+        );
+    }
+
+    #[test]
+    fn test_walker_files_and_dirs_only() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+        let files = setup.files_in_root + setup.dir_count * setup.files_per_subdir;
+
+        let walker = Walker::new(setup.path())
+            .files_only(true)
+            .walk()
+            .expect("Failed to create walker");
+        assert_eq!(walker.count(), files);
+
+        let walker = Walker::new(setup.path())
+            .dirs_only(true)
+            .walk()
+            .expect("Failed to create walker");
+        assert_eq!(walker.count(), setup.dir_count);
+
+        let entries = Walker::new(setup.path())
+            .files_only(true)
+            .par_walk()
+            .expect("Failed to walk directories");
+        assert!(entries.iter().all(|e| e.path().is_file()));
+        assert_eq!(entries.len(), files);
+
+        // the last one set wins
+        let entries = Walker::new(setup.path())
+            .files_only(true)
+            .dirs_only(true)
+            .par_walk()
+            .expect("Failed to walk directories");
+        assert!(entries.iter().all(|e| e.path().is_dir()));
+        assert_eq!(entries.len(), setup.dir_count);
+
+        let entries = Walker::new(setup.path())
+            .dirs_only(true)
+            .dirs_only(false)
+            .par_walk()
+            .expect("Failed to walk directories");
+        assert_eq!(entries.len(), setup.entries_count());
+    }
+
+    #[test]
+    fn test_walker_filter() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+        let big_file = setup.path().join("big.txt");
+        std::fs::write(&big_file, pad_content(setup.file_size * 2)).expect("Failed to write file");
+
+        let threshold = setup.file_size;
+        let walker = Walker::new(setup.path()).filter(move |e| {
+            e.metadata()
+                .is_ok_and(|m| m.is_file() && m.len() > threshold)
+        });
+
+        let entries = walker.par_walk().expect("Failed to walk directories");
+        assert_eq!(
+            entries.iter().map(DirEntry::path).collect::<Vec<_>>(),
+            vec![big_file.clone()]
+        );
+
+        let entries: Vec<PathBuf> = walker
+            .walk()
+            .expect("Failed to create walker")
+            .map(|e| e.expect("Failed to read entry").path())
+            .collect();
+        assert_eq!(entries, vec![big_file]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_walker_symlink_loop() {
+        use std::os::unix::fs::symlink;
+
+        // root
+        // └── a
+        //     ├── file.txt
+        //     └── loop -> root
+        let root = tempfile::tempdir().expect("Failed to create tempdir");
+        let a = root.path().join("a");
+        create_dir_all(&a).expect("Failed to create directory");
+        std::fs::write(a.join("file.txt"), "content").expect("Failed to write file");
+        symlink(root.path(), a.join("loop")).expect("Failed to create symlink");
+
+        for follow_links in [false, true] {
+            let walker = Walker::new(root.path())
+                .follow_links(follow_links)
+                .walk()
+                .expect("Failed to create walker");
+            assert_eq!(walker.count(), 3, "walk with follow_links({follow_links})");
+
+            let entries = Walker::new(root.path())
+                .follow_links(follow_links)
+                .par_walk()
+                .expect("Failed to walk directories");
+            assert_eq!(
+                entries.len(),
+                3,
+                "par_walk with follow_links({follow_links})"
+            );
+        }
+
+        // a link to a directory outside of the walked one is only walked into when following links
+        let outside = tempfile::tempdir().expect("Failed to create tempdir");
+        std::fs::write(outside.path().join("other.txt"), "content").expect("Failed to write file");
+        symlink(outside.path(), a.join("outside")).expect("Failed to create symlink");
+
+        for (follow_links, expected) in [(false, 4), (true, 5)] {
+            let walker = Walker::new(root.path())
+                .follow_links(follow_links)
+                .walk()
+                .expect("Failed to create walker");
+            assert_eq!(
+                walker.count(),
+                expected,
+                "walk with follow_links({follow_links})"
+            );
+
+            let entries = Walker::new(root.path())
+                .follow_links(follow_links)
+                .par_walk()
+                .expect("Failed to walk directories");
+            assert_eq!(
+                entries.len(),
+                expected,
+                "par_walk with follow_links({follow_links})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_walker_max_depth() {
+        // root
+        // ├── a.txt
+        // └── d1
+        //     ├── b.txt
+        //     └── d2
+        //         ├── c.txt
+        //         └── d3
+        //             └── e.txt
+        let root = tempfile::tempdir().expect("Failed to create tempdir");
+        let d3 = root.path().join("d1").join("d2").join("d3");
+        create_dir_all(&d3).expect("Failed to create directories");
+        for file in [
+            root.path().join("a.txt"),
+            root.path().join("d1").join("b.txt"),
+            root.path().join("d1").join("d2").join("c.txt"),
+            d3.join("e.txt"),
+        ] {
+            std::fs::write(file, "content").expect("Failed to write file");
+        }
+
+        for (depth, expected) in [(0, 0), (1, 2), (2, 4), (3, 6), (4, 7)] {
+            let walker = Walker::new(root.path())
+                .max_depth(depth)
+                .walk()
+                .expect("Failed to create walker");
+            assert_eq!(walker.count(), expected, "walk with max depth {depth}");
+
+            let entries = Walker::new(root.path())
+                .max_depth(depth)
+                .par_walk()
+                .expect("Failed to walk directories");
+            assert_eq!(entries.len(), expected, "par_walk with max depth {depth}");
+        }
+    }
+
+    #[test]
+    fn test_walker_with_depth() {
+        let setup = TempdirSetupBuilder::new()
+            .dir_count(2)
+            .depth(3)
+            .file_count(14)
+            .root_file_percentage(0.15)
+            .build()
+            .expect("Failed to build tempdir setup");
+        let expected_depth = |entry: &DirEntry| {
+            let relative = entry
+                .path()
+                .strip_prefix(setup.path())
+                .expect("Entry outside of the root")
+                .to_path_buf();
+            relative.components().count() - 1
+        };
+
+        let entries = Walker::new(setup.path())
+            .par_walk_with_depth()
+            .expect("Failed to walk directories");
+        assert_eq!(entries.len(), setup.entries_count());
+        for (entry, depth) in &entries {
+            assert_eq!(*depth, expected_depth(entry), "{}", entry.path().display());
+        }
+
+        // each level has the files of its directories and the directories of the next level
+        let mut counts = [0; 4];
+        for (_, depth) in &entries {
+            counts[*depth] += 1;
+        }
+        let (dirs, files) = (setup.dir_count, setup.files_per_subdir);
+        assert_eq!(
+            counts,
+            [
+                setup.files_in_root + dirs,
+                dirs * (files + 1),
+                dirs * (files + 1),
+                dirs * files
+            ]
+        );
+
+        let entries: Vec<(DirEntry, usize)> = Walker::new(setup.path())
+            .walk_with_depth()
+            .expect("Failed to create walker")
+            .collect::<std::io::Result<_>>()
+            .expect("Failed to walk directories");
+        assert_eq!(entries.len(), setup.entries_count());
+        for (entry, depth) in &entries {
+            assert_eq!(*depth, expected_depth(entry), "{}", entry.path().display());
+        }
+
+        let entries = Walker::new(setup.path())
+            .max_depth(2)
+            .par_walk_with_depth()
+            .expect("Failed to walk directories");
+        assert!(entries.iter().all(|(_, depth)| *depth < 2));
+    }
+
+    #[test]
+    #[cfg(feature = "pattern")]
+    fn test_walker_glob() {
+        let root = tempfile::tempdir().expect("Failed to create tempdir");
+        let sub = root.path().join("logs");
+        create_dir_all(&sub).expect("Failed to create directory");
+        for file in [
+            root.path().join("a.txt"),
+            root.path().join("a.log"),
+            sub.join("b.txt"),
+            sub.join("b.log"),
+            sub.join("c.log"),
+        ] {
+            std::fs::write(file, "content").expect("Failed to write file");
+        }
+
+        let mut walked: Vec<PathBuf> = Walker::new(root.path())
+            .glob("*.txt")
+            .walk()
+            .expect("Failed to create walker")
+            .map(|e| e.expect("Failed to read entry").path())
+            .collect();
+        let mut par_walked: Vec<PathBuf> = Walker::new(root.path())
+            .glob("*.txt")
+            .par_walk()
+            .expect("Failed to walk directories")
+            .iter()
+            .map(DirEntry::path)
+            .collect();
+        walked.sort();
+        par_walked.sort();
+
+        let expected = vec![root.path().join("a.txt"), sub.join("b.txt")];
+        assert_eq!(walked, expected);
+        assert_eq!(par_walked, expected);
+
+        assert!(Walker::new(root.path()).glob("file[z-a]").walk().is_err());
+        assert_eq!(
+            Walker::new(root.path())
+                .glob("file[z-a]")
+                .par_walk()
+                .expect_err("Invalid glob walked")
+                .downcast::<FsError>()
+                .expect("Not an FsError"),
+            FsError::InvalidGlob(GlobMatcher::new("file[z-a]").expect_err("Glob is valid"))
+        );
+
+        let bak_root = tempfile::tempdir().expect("Failed to create tempdir");
+        std::fs::write(bak_root.path().join("x.txt.bak"), "content").expect("Failed to write file");
+        assert_eq!(
+            Walker::new(bak_root.path())
+                .glob("*.txt")
+                .walk()
+                .expect("Failed to create walker")
+                .count(),
+            0
+        );
+        assert!(Walker::new(bak_root.path())
+            .glob("*.txt")
+            .par_walk()
+            .expect("Failed to walk directories")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_walker_extensions() {
+        let root = tempfile::tempdir().expect("Failed to create tempdir");
+        let sub = root.path().join("src");
+        create_dir_all(&sub).expect("Failed to create directory");
+        for file in [
+            root.path().join("Cargo.toml"),
+            root.path().join("README.md"),
+            root.path().join("LICENSE"),
+            sub.join("lib.rs"),
+            sub.join("MAIN.RS"),
+            sub.join("notes.txt"),
+        ] {
+            std::fs::write(file, "content").expect("Failed to write file");
+        }
+
+        let mut walked: Vec<PathBuf> = Walker::new(root.path())
+            .extensions(&["rs", "toml"])
+            .walk()
+            .expect("Failed to create walker")
+            .map(|e| e.expect("Failed to read entry").path())
+            .collect();
+        let mut par_walked: Vec<PathBuf> = Walker::new(root.path())
+            .extensions(&["rs", "toml"])
+            .par_walk()
+            .expect("Failed to walk directories")
+            .iter()
+            .map(DirEntry::path)
+            .collect();
+        walked.sort();
+        par_walked.sort();
+
+        let expected = vec![
+            root.path().join("Cargo.toml"),
+            sub.clone(),
+            sub.join("MAIN.RS"),
+            sub.join("lib.rs"),
+        ];
+        assert_eq!(walked, expected);
+        assert_eq!(par_walked, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walker_par_walk_collect_errors() {
+        use std::{
+            fs::Permissions,
+            os::unix::{fs::symlink, fs::PermissionsExt, net::UnixListener},
+        };
+
+        // root
+        // ├── a
+        // │   ├── file.txt
+        // │   └── loop -> root
+        // └── socket
+        let root = tempfile::tempdir().expect("Failed to create tempdir");
+        let a = root.path().join("a");
+        let socket = root.path().join("socket");
+        create_dir_all(&a).expect("Failed to create directory");
+        std::fs::write(a.join("file.txt"), "content").expect("Failed to write file");
+        symlink(root.path(), a.join("loop")).expect("Failed to create symlink");
+        let _listener = UnixListener::bind(&socket).expect("Failed to create socket");
+
+        let (entries, errors) = Walker::new(root.path())
+            .follow_links(true)
+            .sorted(true)
+            .par_walk_collect_errors();
+        let paths: Vec<PathBuf> = entries.iter().map(DirEntry::path).collect();
+        assert_eq!(paths, vec![a.clone(), a.join("file.txt"), a.join("loop")]);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors.contains(&FsError::symlink_loop(a.join("loop"))));
+        assert!(errors.contains(&FsError::non_file_non_dir(&socket)));
+
+        let missing = root.path().join("missing");
+        let (entries, errors) = Walker::new(&missing).par_walk_collect_errors();
+        assert!(entries.is_empty());
+        assert_eq!(errors, vec![FsError::path_does_not_exist(&missing)]);
+
+        // root can still read a directory without permissions so this is only checked as a regular user
+        let locked = root.path().join("locked");
+        create_dir_all(&locked).expect("Failed to create directory");
+        std::fs::set_permissions(&locked, Permissions::from_mode(0o000))
+            .expect("Failed to set permissions");
+        if read_dir(&locked).is_err() {
+            let (entries, errors) = Walker::new(root.path()).par_walk_collect_errors();
+            assert!(entries.iter().any(|e| e.path() == locked));
+            assert!(errors.contains(&FsError::dir_read(&locked)));
+        }
+        std::fs::set_permissions(&locked, Permissions::from_mode(0o755))
+            .expect("Failed to set permissions");
+    }
+
+    #[cfg(feature = "gitignore")]
+    #[test]
+    fn test_walker_respect_gitignore() {
+        let root = tempfile::tempdir().expect("Failed to create tempdir");
+        let sub = root.path().join("sub");
+        let target = root.path().join("target");
+        create_dir_all(&sub).expect("Failed to create directory");
+        create_dir_all(&target).expect("Failed to create directory");
+        for (file, content) in [
+            (root.path().join(".gitignore"), "*.log\ntarget/\n"),
+            (root.path().join("debug.log"), "content"),
+            (root.path().join("keep.txt"), "content"),
+            (root.path().join("cache.tmp"), "content"),
+            (target.join("out.txt"), "content"),
+            (sub.join(".gitignore"), "*.tmp\n!important.log\n"),
+            (sub.join("trace.log"), "content"),
+            (sub.join("important.log"), "content"),
+            (sub.join("cache.tmp"), "content"),
+            (sub.join("lib.rs"), "content"),
+        ] {
+            std::fs::write(file, content).expect("Failed to write file");
+        }
+
+        let mut walked: Vec<PathBuf> = Walker::new(root.path())
+            .respect_gitignore(true)
+            .walk()
+            .expect("Failed to create walker")
+            .map(|e| e.expect("Failed to read entry").path())
+            .collect();
+        let mut par_walked: Vec<PathBuf> = Walker::new(root.path())
+            .respect_gitignore(true)
+            .par_walk()
+            .expect("Failed to walk directories")
+            .iter()
+            .map(DirEntry::path)
+            .collect();
+        walked.sort();
+        par_walked.sort();
+
+        let expected = vec![
+            root.path().join(".gitignore"),
+            root.path().join("cache.tmp"),
+            root.path().join("keep.txt"),
+            sub.clone(),
+            sub.join(".gitignore"),
+            sub.join("important.log"),
+            sub.join("lib.rs"),
+        ];
+        assert_eq!(walked, expected);
+        assert_eq!(par_walked, expected);
+        assert_eq!(
+            Walker::new(root.path())
+                .respect_gitignore(true)
+                .par_count()
+                .expect("Failed to count entries"),
+            expected.len()
+        );
+        assert_eq!(
+            Walker::new(root.path())
+                .par_count()
+                .expect("Failed to count entries"),
+            12
+        );
+    }
+
+    #[test]
+    fn test_copy_dir() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+        let dst = tempfile::tempdir().expect("Failed to create destination");
+        let dst_path = dst.path().join("copy");
+
+        let copied = std::sync::atomic::AtomicUsize::new(0);
+        let bytes = copy_dir(setup.path(), &dst_path, |_| {
+            copied.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        })
+        .expect("Failed to copy directory");
+
+        let files = setup.files_in_root + setup.dir_count * setup.files_per_subdir;
+        assert_eq!(copied.into_inner(), files);
+        assert_eq!(bytes, files as u64 * setup.file_size);
+
+        let entries = Walker::new(&dst_path)
+            .par_walk()
+            .expect("Failed to walk copy");
+        assert_eq!(entries.len(), setup.entries_count());
+        for entry in entries.iter().filter(|e| e.path().is_file()) {
+            assert_eq!(
+                entry.metadata().expect("Failed to read metadata").len(),
+                setup.file_size
+            );
+        }
+    }
+
+    #[test]
+    fn test_copy_dir_into_itself() {
+        let setup = TempdirSetupBuilder::new()
+            .build()
+            .expect("Failed to build tempdir setup");
+        let err = copy_dir(setup.path(), setup.path().join("copy"), |_| {})
+            .expect_err("Copying into itself should fail");
+
+        assert_eq!(
+            err.downcast::<FsError>().expect("Expected an FsError"),
+            FsError::destination_inside_source(setup.path().join("copy"))
+        );
+        assert!(!setup.path().join("copy").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_skips_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        // src
+        // ├── file.txt
+        // ├── file_link -> file.txt
+        // └── sub
+        //     └── dir_link -> ..
+        let src = tempfile::tempdir().expect("Failed to create source");
+        let sub = src.path().join("sub");
+        create_dir_all(&sub).expect("Failed to create directory");
+        std::fs::write(src.path().join("file.txt"), "content").expect("Failed to write file");
+        symlink(src.path().join("file.txt"), src.path().join("file_link"))
+            .expect("Failed to create symlink");
+        symlink(src.path(), sub.join("dir_link")).expect("Failed to create symlink");
+
+        let dst = tempfile::tempdir().expect("Failed to create destination");
+        let dst_path = dst.path().join("copy");
+        let copied = std::sync::Mutex::new(Vec::new());
+        let bytes = copy_dir(src.path(), &dst_path, |path| {
+            copied
+                .lock()
+                .expect("Failed to lock")
+                .push(path.to_path_buf());
+        })
+        .expect("Failed to copy directory");
+
+        assert_eq!(bytes, "content".len() as u64);
+        assert_eq!(
+            copied.into_inner().expect("Failed to lock"),
+            vec![src.path().join("file.txt")]
+        );
+        assert!(dst_path.join("file.txt").is_file());
+        assert!(dst_path.join("sub").is_dir());
+        assert!(dst_path.join("file_link").symlink_metadata().is_err());
+        assert!(dst_path.join("sub/dir_link").symlink_metadata().is_err());
+    }
 }
@@ -41,11 +41,12 @@
 //! ```
 
 use anyhow::{anyhow, Context, Result};
-use rayon::iter::{ParallelBridge, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::{
     fs::{create_dir, File},
     io::{BufWriter, Write},
     path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 use tempfile::{tempdir, TempDir};
 
@@ -53,18 +54,34 @@ use tempfile::{tempdir, TempDir};
 #[derive(Debug)]
 pub struct TempdirSetupBuilder {
     dir_count: usize,
+    depth: usize,
     file_count: usize,
     file_size: u64,
+    file_size_range: Option<(u64, u64)>,
     root_file_perc: f64,
+    seed: Option<u64>,
+    file_extension: String,
+    file_prefix: String,
+    dir_prefix: String,
+    #[cfg(unix)]
+    symlink_count: usize,
 }
 
 impl Default for TempdirSetupBuilder {
     fn default() -> Self {
         Self {
             dir_count: 5,
+            depth: 1,
             file_count: 15,
             file_size: 128,
+            file_size_range: None,
             root_file_perc: 0.15,
+            seed: None,
+            file_extension: "txt".to_string(),
+            file_prefix: "file".to_string(),
+            dir_prefix: "dir".to_string(),
+            #[cfg(unix)]
+            symlink_count: 0,
         }
     }
 }
@@ -97,6 +114,29 @@ impl TempdirSetupBuilder {
         self
     }
 
+    /// Sets how deep the sub-directories go, each sub-directory of the root gets a chain of `depth - 1` nested
+    /// sub-directories with the same name (e.g. `dir0/dir0/dir0` for a depth of `3`) and the files are divided among
+    /// all of them
+    ///
+    /// Default is `1`
+    ///
+    /// ## Arguments
+    ///
+    /// * `depth` - The depth of the sub-directories
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::helper::TempdirSetupBuilder;
+    ///
+    /// let setup = TempdirSetupBuilder::new().depth(3).build().unwrap(); // dir0/dir0/dir0, dir1/dir1/dir1, ...
+    /// ```
+    #[must_use]
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
     /// Sets the number of files to create in each sub-directory
     ///
     /// Default is `15`
@@ -139,6 +179,29 @@ impl TempdirSetupBuilder {
         self
     }
 
+    /// Sets the range of sizes in bytes of the files, each file gets a size between `min` and `max` (inclusive) picked
+    /// with the [seed](TempdirSetupBuilder::seed) if set or randomly otherwise. Overrides [`TempdirSetupBuilder::file_size`].
+    ///
+    /// Default is `None`
+    ///
+    /// ## Arguments
+    ///
+    /// * `min` - The minimum size in bytes of each file
+    /// * `max` - The maximum size in bytes of each file
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::helper::TempdirSetupBuilder;
+    ///
+    /// let setup = TempdirSetupBuilder::new().file_size_range(1024, 1048576).build().unwrap(); // 1KB to 1MB
+    /// ```
+    #[must_use]
+    pub fn file_size_range(mut self, min: u64, max: u64) -> Self {
+        self.file_size_range = Some((min, max));
+        self
+    }
+
     /// Sets the arguments to use for `dir_count`, `file_count`, `file_size` and `root_file_perc` in that order. The recommended is to use the individual methods: [`TempdirSetupBuilder::dir_count`], [`TempdirSetupBuilder::file_count`], [`TempdirSetupBuilder::file_size`] and [`TempdirSetupBuilder::root_file_percentage`].
     ///
     /// ## Arguments
@@ -182,6 +245,113 @@ impl TempdirSetupBuilder {
         self
     }
 
+    /// Sets the seed used to fill the files with pseudo-random bytes, the same seed always creates the same files.
+    ///
+    /// Default is `None`, which fills every file with the same [padded content](pad_content)
+    ///
+    /// ## Arguments
+    ///
+    /// * `seed` - The seed for the file contents
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::helper::TempdirSetupBuilder;
+    ///
+    /// let setup = TempdirSetupBuilder::new().seed(42).build().unwrap();
+    /// ```
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the extension of the files, with or without the leading dot, an empty extension creates files without one
+    ///
+    /// Default is `txt`
+    ///
+    /// ## Arguments
+    ///
+    /// * `extension` - The extension of the files
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::helper::TempdirSetupBuilder;
+    ///
+    /// let setup = TempdirSetupBuilder::new().file_extension("rs").build().unwrap(); // file0.rs, file1.rs, ...
+    /// ```
+    #[must_use]
+    pub fn file_extension(mut self, extension: &str) -> Self {
+        self.file_extension = extension.trim_start_matches('.').to_string();
+        self
+    }
+
+    /// Sets the prefix of the file names, followed by the file's index
+    ///
+    /// Default is `file`
+    ///
+    /// ## Arguments
+    ///
+    /// * `prefix` - The prefix of the file names
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::helper::TempdirSetupBuilder;
+    ///
+    /// let setup = TempdirSetupBuilder::new().file_prefix("lib").build().unwrap(); // lib0.txt, lib1.txt, ...
+    /// ```
+    #[must_use]
+    pub fn file_prefix(mut self, prefix: &str) -> Self {
+        self.file_prefix = prefix.to_string();
+        self
+    }
+
+    /// Sets the prefix of the sub-directory names, followed by the sub-directory's index
+    ///
+    /// Default is `dir`
+    ///
+    /// ## Arguments
+    ///
+    /// * `prefix` - The prefix of the sub-directory names
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::helper::TempdirSetupBuilder;
+    ///
+    /// let setup = TempdirSetupBuilder::new().dir_prefix("module_").build().unwrap(); // module_0, module_1, ...
+    /// ```
+    #[must_use]
+    pub fn dir_prefix(mut self, prefix: &str) -> Self {
+        self.dir_prefix = prefix.to_string();
+        self
+    }
+
+    /// Sets the number of symbolic links to create in the root directory, named `link0`, `link1`, etc. and each
+    /// pointing to one of the created files
+    ///
+    /// Default is `0`
+    ///
+    /// ## Arguments
+    ///
+    /// * `count` - The number of symbolic links to create
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::helper::TempdirSetupBuilder;
+    ///
+    /// let setup = TempdirSetupBuilder::new().symlink_count(3).build().unwrap(); // link0, link1 and link2
+    /// ```
+    #[cfg(unix)]
+    #[must_use]
+    pub fn symlink_count(mut self, count: usize) -> Self {
+        self.symlink_count = count;
+        self
+    }
+
     /// Builds the [`TempdirSetup`]
     ///
     /// ## Returns
@@ -211,12 +381,20 @@ impl TempdirSetupBuilder {
             self.root_file_perc,
         );
 
-        if dirs == 0 || files == 0 || size == 0 {
+        if dirs == 0 || files == 0 || size == 0 || self.depth == 0 {
             return Err(anyhow!(
-                "Directory count, file count and file size must be greater than 0"
+                "Directory count, depth, file count and file size must be greater than 0"
             ));
         }
 
+        if let Some((min, max)) = self.file_size_range {
+            if min == 0 || min > max {
+                return Err(anyhow!(
+                    "File size range must have a minimum greater than 0 and not greater than the maximum"
+                ));
+            }
+        }
+
         if files > F64_MAX_EXACT_INT {
             return Err(anyhow!("File count must be less than {F64_MAX_EXACT_INT}"));
         }
@@ -235,57 +413,94 @@ impl TempdirSetupBuilder {
         )]
         let root_files = (self.root_file_perc * files as f64).round() as usize;
         let total_files = files - root_files;
-        let files_per_dir = total_files / dirs;
-
-        (0..root_files)
-            .par_bridge()
+        let depth = self.depth;
+        let files_per_dir = total_files / (dirs * depth);
+        let size_seed = self.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs() ^ u64::from(d.subsec_nanos()))
+        });
+        let extension = if self.file_extension.is_empty() {
+            String::new()
+        } else {
+            format!(".{}", self.file_extension)
+        };
+        let (file_prefix, dir_prefix) = (&self.file_prefix, &self.dir_prefix);
+        let content = |index: usize| self.file_content(index as u64, size_seed);
+
+        let mut file_paths = (0..root_files)
+            .into_par_iter()
             .map(|i| {
-                let file_path = &temp_path.join(format!("file{i}.txt"));
-                let mut file = BufWriter::new(
-                    File::create(file_path).context("Failed to create temporary file")?,
-                );
-
-                file.write_all(&pad_content(size))
-                    .context("Failed to write to temporary file")?;
-                file.flush().context("Failed to flush temporary file")?;
-                drop(file);
-                Ok(())
+                let file_path = temp_path.join(format!("{file_prefix}{i}{extension}"));
+                write_file(&file_path, &content(i))?;
+                Ok(file_path)
             })
             .collect::<Result<Vec<_>>>()?;
 
-        (0..dirs)
-            .par_bridge()
+        let dirs_with_files = (0..dirs)
+            .into_par_iter()
             .map(|i| {
-                let dir = &temp_path.join(format!("dir{i}"));
-                create_dir(dir).context("Failed to create temporary directory")?;
-
-                for j in 0..files_per_dir {
-                    let file_path = &dir.join(format!("file{i}_{j}.txt"));
-                    let mut file = BufWriter::new(
-                        File::create(file_path).context("Failed to create temporary file")?,
-                    );
-
-                    file.write_all(&pad_content(size))
-                        .context("Failed to write to temporary file")?;
-                    file.flush().context("Failed to flush temporary file")?;
-                    drop(file);
+                let mut dir = temp_path.clone();
+                let mut levels = Vec::with_capacity(depth);
+
+                for level in 0..depth {
+                    dir = dir.join(format!("{dir_prefix}{i}"));
+                    create_dir(&dir).context("Failed to create temporary directory")?;
+
+                    let first_index = root_files + (i * depth + level) * files_per_dir;
+                    let mut files = Vec::with_capacity(files_per_dir);
+                    for j in 0..files_per_dir {
+                        let file_path = dir.join(format!("{file_prefix}{i}_{j}{extension}"));
+                        write_file(&file_path, &content(first_index + j))?;
+                        files.push(file_path);
+                    }
+
+                    levels.push((dir.clone(), files));
                 }
 
-                Ok(())
+                Ok(levels)
             })
             .collect::<Result<Vec<_>>>()?;
 
+        let (dir_paths, sub_file_paths): (Vec<PathBuf>, Vec<Vec<PathBuf>>) =
+            dirs_with_files.into_iter().flatten().unzip();
+        file_paths.extend(sub_file_paths.into_iter().flatten());
+
+        #[cfg(unix)]
+        let symlinks = create_symlinks(&temp_path, &file_paths, self.symlink_count)?;
+        #[cfg(not(unix))]
+        let symlinks = Vec::new();
+
         Ok(TempdirSetup {
             dir_count: dirs,
+            depth,
             file_count: files,
             file_size: size,
+            file_size_range: self.file_size_range,
             root_file_percentage: root_perc,
             files_in_root: root_files,
             files_per_subdir: files_per_dir,
+            file_paths,
+            dir_paths,
+            symlinks,
             _temp_dir: temp_dir,
             temp_path,
         })
     }
+
+    /// Generates the content of the file at `index`, picking its size with `size_seed` if there's a size range
+    fn file_content(&self, index: u64, size_seed: u64) -> Vec<u8> {
+        let size = self.file_size_range.map_or(self.file_size, |(min, max)| {
+            // a different stream than the contents so the sizes don't follow them
+            let mut state = !size_seed ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            min + splitmix64(&mut state) % (max - min + 1)
+        });
+
+        match self.seed {
+            Some(seed) => seeded_content(size, seed, index),
+            None => pad_content(size),
+        }
+    }
 }
 
 /// Helper struct to setup a temporary directory, sub-directories and files for testing and benchmarking
@@ -308,30 +523,41 @@ pub struct TempdirSetup {
     _temp_dir: TempDir,
     temp_path: PathBuf,
 
-    /// The number of directories to create
+    /// The number of directories to create in the root directory
     pub dir_count: usize,
 
+    /// How deep the sub-directories go, each directory in the root has `depth - 1` nested sub-directories
+    pub depth: usize,
+
     /// The number of files to create in each directory
     pub file_count: usize,
 
-    /// The size in bytes of each file
+    /// The size in bytes of each file, unless [`TempdirSetup::file_size_range`] is set
     pub file_size: u64,
 
+    /// The minimum and maximum size in bytes of the files
+    pub file_size_range: Option<(u64, u64)>,
+
     /// The percentage of files to create in the root directory
     pub root_file_percentage: f64,
 
     /// The number of files to create in the root directory
     pub files_in_root: usize,
 
-    /// The number of files to create in each sub-directory
+    /// The number of files to create in each sub-directory, at every depth
     pub files_per_subdir: usize,
+
+    file_paths: Vec<PathBuf>,
+    dir_paths: Vec<PathBuf>,
+    symlinks: Vec<PathBuf>,
 }
 
 impl TempdirSetup {
     /// Returns the number of entries in the temporary directory
     #[must_use]
     pub fn entries_count(&self) -> usize {
-        self.dir_count * self.files_per_subdir + self.dir_count + self.files_in_root
+        let subdirs = self.dir_count * self.depth;
+        subdirs * self.files_per_subdir + subdirs + self.files_in_root + self.symlinks.len()
     }
 
     /// Returns the path to the temporary directory
@@ -339,6 +565,57 @@ impl TempdirSetup {
     pub fn path(&self) -> &Path {
         &self.temp_path
     }
+
+    /// Returns the paths of the created files, the ones in the root directory first and then the ones in each
+    /// sub-directory in order
+    #[must_use]
+    pub fn file_paths(&self) -> &[PathBuf] {
+        &self.file_paths
+    }
+
+    /// Returns the paths of the created sub-directories in order, each directory in the root followed by its nested
+    /// sub-directories
+    #[must_use]
+    pub fn dir_paths(&self) -> &[PathBuf] {
+        &self.dir_paths
+    }
+
+    /// Returns the paths of the created symbolic links, always empty if not on Unix
+    #[must_use]
+    pub fn symlinks(&self) -> &[PathBuf] {
+        &self.symlinks
+    }
+}
+
+/// Creates `count` symbolic links in `root` pointing to the `targets` in order, wrapping around if there are more links
+/// than targets
+#[cfg(unix)]
+fn create_symlinks(root: &Path, targets: &[PathBuf], count: usize) -> Result<Vec<PathBuf>> {
+    if count > 0 && targets.is_empty() {
+        return Err(anyhow!("Symbolic links need at least one file to point to"));
+    }
+
+    targets
+        .iter()
+        .cycle()
+        .take(count)
+        .enumerate()
+        .map(|(i, target)| {
+            let link = root.join(format!("link{i}"));
+            std::os::unix::fs::symlink(target, &link)
+                .context("Failed to create temporary symbolic link")?;
+            Ok(link)
+        })
+        .collect()
+}
+
+/// Creates a file at `path` with the given content
+fn write_file(path: &Path, content: &[u8]) -> Result<()> {
+    let mut file = BufWriter::new(File::create(path).context("Failed to create temporary file")?);
+
+    file.write_all(content)
+        .context("Failed to write to temporary file")?;
+    file.flush().context("Failed to flush temporary file")
 }
 
 /// Helper function to pad content to a specific length
@@ -367,7 +644,33 @@ pub fn pad_content(n: u64) -> Vec<u8> {
     const CONTENT: &[u8] = "asdaodkoodoakwodokaokwdokoakowkdokaowkdkoakwodkoakodwkokaodwkooakwodoaokwodkokaowkdkowkodkakowodkoakowkdokoakwdkwkadkwdkoawkdokw".as_bytes();
     assert!(n > 0, "n must be greater than 0");
 
-    CONTENT.repeat(usize::try_from(n).expect("Failed to convert u64 to usize") / CONTENT.len())
+    let n = usize::try_from(n).expect("Failed to convert u64 to usize");
+    let mut content = CONTENT.repeat(n / CONTENT.len());
+    content.extend_from_slice(&CONTENT[..n % CONTENT.len()]);
+    content
+}
+
+/// Generates `n` pseudo-random bytes that only depend on `seed` and `index`, using `SplitMix64`
+fn seeded_content(n: u64, seed: u64, index: u64) -> Vec<u8> {
+    let len = usize::try_from(n).expect("Failed to convert u64 to usize");
+    let mut state = seed ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    let mut content = Vec::with_capacity(len + 8);
+
+    while content.len() < len {
+        content.extend_from_slice(&splitmix64(&mut state).to_le_bytes());
+    }
+
+    content.truncate(len);
+    content
+}
+
+/// Advances the `SplitMix64` state and returns the next pseudo-random number
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
 #[cfg(test)]
@@ -391,6 +694,217 @@ mod tests {
         assert_eq!(entries.len(), setup.entries_count());
     }
 
+    #[test]
+    fn test_tempdir_setup_seed() {
+        let build = |seed| {
+            TempdirSetupBuilder::new()
+                .file_size(1000)
+                .seed(seed)
+                .build()
+                .expect("Failed to build tempdir setup")
+        };
+        let read = |setup: &TempdirSetup, file: &str| {
+            std::fs::read(setup.path().join(file)).expect("Failed to read file")
+        };
+
+        let (first, second, other) = (build(42), build(42), build(7));
+        for file in [
+            "file0.txt",
+            "file1.txt",
+            "dir0/file0_0.txt",
+            "dir4/file4_1.txt",
+        ] {
+            let content = read(&first, file);
+            assert_eq!(content.len(), 1000);
+            assert_eq!(content, read(&second, file), "{file}");
+            assert_ne!(content, read(&other, file), "{file}");
+        }
+
+        // files are different from each other
+        assert_ne!(read(&first, "file0.txt"), read(&first, "file1.txt"));
+        assert_ne!(
+            read(&first, "dir0/file0_0.txt"),
+            read(&first, "dir0/file0_1.txt")
+        );
+    }
+
+    #[test]
+    fn test_tempdir_setup_names() {
+        let setup = TempdirSetupBuilder::new()
+            .file_extension(".log")
+            .file_prefix("entry")
+            .dir_prefix("module_")
+            .build()
+            .expect("Failed to build tempdir setup");
+
+        let entries = Walker::new(setup.path())
+            .par_walk()
+            .expect("Failed to walk directories");
+        assert_eq!(entries.len(), setup.entries_count());
+
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() {
+                assert!(name.starts_with("module_"), "{name}");
+            } else {
+                assert!(name.starts_with("entry"), "{name}");
+                assert_eq!(entry.path().extension(), Some("log".as_ref()), "{name}");
+            }
+        }
+        assert!(setup.path().join("module_0").join("entry0_0.log").is_file());
+
+        let setup = TempdirSetupBuilder::new()
+            .file_extension("")
+            .build()
+            .expect("Failed to build tempdir setup");
+        assert!(setup.path().join("file0").is_file());
+    }
+
+    #[test]
+    fn test_tempdir_setup_file_size_range() {
+        let build = |seed| {
+            TempdirSetupBuilder::new()
+                .file_count(50)
+                .file_size_range(100, 2000)
+                .seed(seed)
+                .build()
+                .expect("Failed to build tempdir setup")
+        };
+        let sizes = |setup: &TempdirSetup| {
+            let mut sizes: Vec<(PathBuf, u64)> = Walker::new(setup.path())
+                .files_only(true)
+                .par_walk()
+                .expect("Failed to walk directories")
+                .iter()
+                .map(|e| {
+                    (
+                        e.path(),
+                        e.metadata().expect("Failed to read metadata").len(),
+                    )
+                })
+                .collect();
+            sizes.sort();
+            sizes
+                .into_iter()
+                .map(|(path, size)| (path.strip_prefix(setup.path()).unwrap().to_path_buf(), size))
+                .collect::<Vec<_>>()
+        };
+
+        let setup = build(42);
+        let files = setup.files_in_root + setup.dir_count * setup.files_per_subdir;
+        let first = sizes(&setup);
+        assert_eq!(first.len(), files);
+        assert!(first.iter().all(|(_, size)| (100..=2000).contains(size)));
+        assert!(first.iter().any(|(_, size)| *size != first[0].1));
+
+        let total = Walker::new(setup.path())
+            .total_size()
+            .expect("Failed to get total size");
+        assert!(total >= 100 * files as u64 && total <= 2000 * files as u64);
+        assert_eq!(first, sizes(&build(42)));
+
+        let unseeded = TempdirSetupBuilder::new()
+            .file_size_range(10, 10)
+            .build()
+            .expect("Failed to build tempdir setup");
+        assert!(sizes(&unseeded).iter().all(|(_, size)| *size == 10));
+
+        assert!(TempdirSetupBuilder::new()
+            .file_size_range(0, 10)
+            .build()
+            .is_err());
+        assert!(TempdirSetupBuilder::new()
+            .file_size_range(20, 10)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_tempdir_setup_paths() {
+        let setup = TempdirSetupBuilder::new()
+            .dir_count(5)
+            .file_count(20)
+            .root_file_percentage(0.25)
+            .build()
+            .expect("Failed to build tempdir setup");
+
+        let files = setup.file_paths();
+        assert_eq!(files.len(), setup.file_count);
+        assert!(files.iter().all(|f| f.is_file()));
+
+        let in_root: Vec<&PathBuf> = files
+            .iter()
+            .filter(|f| f.parent() == Some(setup.path()))
+            .collect();
+        assert_eq!(in_root.len(), setup.files_in_root);
+        assert_eq!(files[0], setup.path().join("file0.txt"));
+
+        let dirs = setup.dir_paths();
+        assert_eq!(dirs.len(), setup.dir_count);
+        assert!(dirs
+            .iter()
+            .all(|d| d.is_dir() && d.parent() == Some(setup.path())));
+        for dir in dirs {
+            let in_dir = files.iter().filter(|f| f.parent() == Some(dir)).count();
+            assert_eq!(in_dir, setup.files_per_subdir);
+        }
+    }
+
+    #[test]
+    fn test_tempdir_setup_depth() {
+        let setup = TempdirSetupBuilder::new()
+            .dir_count(4)
+            .depth(3)
+            .file_count(30)
+            .root_file_percentage(0.2)
+            .build()
+            .expect("Failed to build tempdir setup");
+        assert_eq!(setup.files_per_subdir, 2);
+
+        let entries = Walker::new(setup.path())
+            .par_walk()
+            .expect("Failed to walk directories");
+        assert_eq!(entries.len(), setup.entries_count());
+        assert_eq!(setup.dir_paths().len(), 12);
+        assert!(setup.path().join("dir0").join("dir0").join("dir0").is_dir());
+
+        let shallow = Walker::new(setup.path())
+            .max_depth(2)
+            .par_walk()
+            .expect("Failed to walk directories");
+        assert_eq!(
+            shallow.len(),
+            setup.files_in_root + setup.dir_count * (setup.files_per_subdir + 2)
+        );
+
+        assert!(TempdirSetupBuilder::new().depth(0).build().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tempdir_setup_symlinks() {
+        let setup = TempdirSetupBuilder::new()
+            .symlink_count(4)
+            .build()
+            .expect("Failed to build tempdir setup");
+
+        let links = setup.symlinks();
+        assert_eq!(links.len(), 4);
+        for link in links {
+            let meta = std::fs::symlink_metadata(link).expect("Failed to read link metadata");
+            assert!(meta.file_type().is_symlink());
+
+            let target = std::fs::read_link(link).expect("Failed to read link");
+            assert!(setup.file_paths().contains(&target));
+            assert!(link.is_file());
+        }
+
+        let entries = Walker::new(setup.path())
+            .par_walk()
+            .expect("Failed to walk directories");
+        assert_eq!(entries.len(), setup.entries_count());
+    }
+
     // TODO add more tests
 
     #[test]
@@ -398,6 +912,8 @@ mod tests {
         assert_eq!(pad_content(128).len(), 128);
         assert_eq!(pad_content(1024).len(), 1024);
         assert_eq!(pad_content(1_048_576).len(), 1_048_576);
+        assert_eq!(pad_content(1).len(), 1);
+        assert_eq!(pad_content(1000).len(), 1000);
     }
 
     #[test]
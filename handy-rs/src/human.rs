@@ -1,3 +1,4 @@
+use crate::{errors::ParseError, parse::split_at_non_digits};
 use num_traits::{AsPrimitive, Zero};
 use std::sync::OnceLock;
 
@@ -35,6 +36,11 @@ pub struct Humanizer {
     units: Vec<String>,
     space_before_unit: bool,
     division_factor: f64,
+    strip_trailing_zeros: bool,
+    precision: Option<usize>,
+    long_units: Option<Vec<String>>,
+    pluralize: bool,
+    grouping: Option<char>,
 }
 
 impl Humanizer {
@@ -60,9 +66,56 @@ impl Humanizer {
             units: units.iter().map(std::string::ToString::to_string).collect(),
             space_before_unit: true,
             division_factor: 1000.0,
+            strip_trailing_zeros: false,
+            precision: None,
+            long_units: None,
+            pluralize: false,
+            grouping: None,
         }
     }
 
+    /// Returns the humanizer used by [`human_number`], with the units `K`, `M`, `B`, `T`, `Qa` and `Qd`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use handy::human::Humanizer;
+    ///
+    /// assert_eq!(Humanizer::number().parse("1.5 K").unwrap(), 1_500.0);
+    /// ```
+    #[must_use]
+    pub fn number() -> &'static Self {
+        num_humanizer()
+    }
+
+    /// Returns the humanizer used by [`human_bytes`], with binary units (`KiB`, `MiB`, etc.).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use handy::human::Humanizer;
+    ///
+    /// assert_eq!(Humanizer::binary().format(12_345), "12.1 KiB");
+    /// ```
+    #[must_use]
+    pub fn binary() -> &'static Self {
+        binary_humanizer()
+    }
+
+    /// Returns the humanizer used by [`human_bytes_si`], with SI units (`KB`, `MB`, etc.).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use handy::human::Humanizer;
+    ///
+    /// assert_eq!(Humanizer::si().format(12_345), "12.3 KB");
+    /// ```
+    #[must_use]
+    pub fn si() -> &'static Self {
+        si_humanizer()
+    }
+
     /// Sets whether or not to add a space before the unit (default: `true`).
     /// Example: `true` -> "1 MB", `false` -> "1MB".
     #[must_use]
@@ -71,12 +124,69 @@ impl Humanizer {
         self
     }
 
+    /// Sets whether or not to remove trailing zeros from the decimal part (default: `false`).
+    /// Example: `true` -> "1.2 K" and "2 K", `false` -> "1.20 K" and "2.00 K".
+    #[must_use]
+    pub fn with_strip_trailing_zeros(mut self, strip_trailing_zeros: bool) -> Self {
+        self.strip_trailing_zeros = strip_trailing_zeros;
+        self
+    }
+
+    /// Sets a fixed number of decimal places, when unset it depends on the value: 2 under 10, 1 under 100 and 0
+    /// otherwise.
+    /// Example: `3` -> "1.235 M" and "635.000", unset -> "1.23 M" and "635".
+    #[must_use]
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the separator inserted every three digits of the integer part (default: `None`), useful when the units
+    /// run out before the number is small.
+    /// Example: `Some(',')` -> "123,456 B", `None` -> "123456 B".
+    #[must_use]
+    pub fn with_grouping(mut self, separator: Option<char>) -> Self {
+        self.grouping = separator;
+        self
+    }
+
+    /// Sets the long unit names used by [`Humanizer::format_long`], one for each of the short units.
+    /// Example: `&["byte", "kilobyte", "megabyte"]` -> "1.2 kilobyte".
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `long_units` doesn't have the same length as the short units.
+    #[must_use]
+    pub fn with_long_units(mut self, long_units: &[&str]) -> Self {
+        assert_eq!(
+            long_units.len(),
+            self.units.len(),
+            "Long units must have the same length as the units"
+        );
+
+        self.long_units = Some(
+            long_units
+                .iter()
+                .map(std::string::ToString::to_string)
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets whether or not to append an `s` to long unit names when the value is not 1 (default: `false`).
+    /// Example: `true` -> "1 byte" and "1.2 kilobytes", `false` -> "1 byte" and "1.2 kilobyte".
+    #[must_use]
+    pub fn with_pluralization(mut self, pluralize: bool) -> Self {
+        self.pluralize = pluralize;
+        self
+    }
+
     /// Sets the division factor between units (default: `1000.0`).
     /// Example: Use `1024.0` for binary prefixes (KiB, MiB, etc.).
     ///
     /// ## Panics
     ///
-    /// Panics if the division factor is less than or equal to 0.
+    /// Panics if the division factor is not greater than 1, since it wouldn't make the number any smaller.
     #[must_use]
     pub fn with_division_factor<F>(mut self, factor: F) -> Self
     where
@@ -84,8 +194,9 @@ impl Humanizer {
     {
         self.division_factor = factor.into();
         assert!(
-            self.division_factor >= 0.0,
-            "Division factor must be greater than 0"
+            self.division_factor > 1.0,
+            "Division factor must be greater than 1, got {}",
+            self.division_factor
         );
         self
     }
@@ -137,27 +248,150 @@ impl Humanizer {
     pub fn format<U>(&self, value: U) -> String
     where
         U: Zero + AsPrimitive<f64> + PartialEq,
+    {
+        let mut s = String::new();
+        let _ = self.format_into(value, &mut s);
+        s
+    }
+
+    /// Formats a number into a human readable string using the humanizer's units, writing it to `w` instead of
+    /// allocating a new string so a buffer can be reused.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use handy::human::Humanizer;
+    ///
+    /// let humanizer = Humanizer::new(&["", "k", "m", "b", "t"]).with_space_before_unit(false);
+    /// let mut buffer = String::new();
+    /// humanizer.format_into(123_456_789, &mut buffer).unwrap();
+    /// assert_eq!(buffer, "123m");
+    /// ```
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to format.
+    /// * `w` - The writer to write the formatted value to.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if writing to `w` fails.
+    pub fn format_into<U, W>(&self, value: U, w: &mut W) -> std::fmt::Result
+    where
+        U: Zero + AsPrimitive<f64> + PartialEq,
+        W: std::fmt::Write,
+    {
+        self.write_with_units(value, &self.units, false, w)
+    }
+
+    /// Formats a number into a human readable string using the humanizer's long unit names, falling back to the short
+    /// units if [`Humanizer::with_long_units`] wasn't used.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use handy::human::Humanizer;
+    ///
+    /// let humanizer = Humanizer::new(&["B", "KB", "MB"])
+    ///     .with_long_units(&["byte", "kilobyte", "megabyte"])
+    ///     .with_pluralization(true)
+    ///     .with_strip_trailing_zeros(true);
+    /// assert_eq!(humanizer.format_long(1), "1 byte");
+    /// assert_eq!(humanizer.format_long(1_200), "1.2 kilobytes");
+    /// ```
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to format.
+    ///
+    /// ## Returns
+    ///
+    /// A human readable string using the humanizer's long unit names.
+    pub fn format_long<U>(&self, value: U) -> String
+    where
+        U: Zero + AsPrimitive<f64> + PartialEq,
+    {
+        match &self.long_units {
+            Some(long_units) => {
+                let mut s = String::new();
+                let _ = self.write_with_units(value, long_units, self.pluralize, &mut s);
+                s
+            }
+            None => self.format(value),
+        }
+    }
+
+    /// Formats a number into a human readable string using the given units and writes it to `w`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to format.
+    /// * `units` - The units to use, with the same length as the humanizer's units.
+    /// * `pluralize` - Whether or not to append an `s` to the unit when the number is not 1.
+    /// * `w` - The writer to write the formatted value to.
+    fn write_with_units<U, W>(
+        &self,
+        value: U,
+        units: &[String],
+        pluralize: bool,
+        w: &mut W,
+    ) -> std::fmt::Result
+    where
+        U: Zero + AsPrimitive<f64> + PartialEq,
+        W: std::fmt::Write,
     {
         let (num_value, index) = self.calculate_parts(value);
-        let unit = &self.units[index];
+        let unit = &units[index];
         let space = if self.space_before_unit && !unit.is_empty() {
             " "
         } else {
             ""
         };
+        let plural = |number: &str| {
+            if pluralize
+                && !unit.is_empty()
+                && number
+                    .parse::<f64>()
+                    .map_or(true, |n| (n.abs() - 1.0).abs() > f64::EPSILON)
+            {
+                "s"
+            } else {
+                ""
+            }
+        };
 
         if index == 0 && num_value == 0.0 {
-            return format!("0{space}{unit}");
+            let suffix = plural("0");
+            return write!(w, "0{space}{unit}{suffix}");
         }
 
         let abs_val = num_value.abs();
-        let precision = if abs_val < 10.0 {
+        let precision = self.precision.unwrap_or(if abs_val < 10.0 {
             2
         } else {
             usize::from(abs_val < 100.0)
-        };
+        });
+
+        let mut number = format!("{num_value:.precision$}");
+        if self.strip_trailing_zeros && number.contains('.') {
+            let stripped_len = number.trim_end_matches('0').trim_end_matches('.').len();
+            number.truncate(stripped_len);
+        }
+
+        // tiny negative values round to zero, which shouldn't keep the sign
+        if number
+            .strip_prefix('-')
+            .is_some_and(|n| n.chars().all(|c| c == '0' || c == '.'))
+        {
+            number.remove(0);
+        }
 
-        format!("{num_value:.precision$}{space}{unit}")
+        let suffix = plural(&number);
+        if let Some(separator) = self.grouping {
+            number = group_digits(&number, separator);
+        }
+
+        write!(w, "{number}{space}{unit}{suffix}")
     }
 
     /// Formats a number into a human readable string using the humanizer's units but returns the value and the unit.
@@ -186,16 +420,86 @@ impl Humanizer {
         let (num_value, index) = self.calculate_parts(value);
         (num_value, &self.units[index])
     }
+
+    /// Parses a human readable string back into a number using the humanizer's units, the opposite of
+    /// [`Humanizer::format`].
+    ///
+    /// The unit is matched exactly first and then ignoring case, a missing unit is treated as the first one.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use handy::human::Humanizer;
+    ///
+    /// let humanizer = Humanizer::new(&["", "k", "m", "b", "t"]);
+    /// assert_eq!(humanizer.parse("1.5 m").unwrap(), 1_500_000.0);
+    /// ```
+    ///
+    /// ## Arguments
+    ///
+    /// * `s` - The string to parse.
+    ///
+    /// ## Returns
+    ///
+    /// The parsed number.
+    ///
+    /// ## Errors
+    ///
+    /// - [`ParseError::InvalidNumber`]: If the number could not be parsed
+    /// - [`ParseError::UnknownUnit`]: If the unit is not one of the humanizer's units
+    pub fn parse(&self, s: &str) -> Result<f64, ParseError> {
+        let (number, unit): (f64, String) = split_at_non_digits(s)?;
+        let unit = unit.trim();
+
+        let index = if unit.is_empty() {
+            0
+        } else {
+            self.units
+                .iter()
+                .position(|u| u == unit)
+                .or_else(|| self.units.iter().position(|u| u.eq_ignore_ascii_case(unit)))
+                .ok_or_else(|| ParseError::UnknownUnit(unit.to_string()))?
+        };
+
+        let exponent =
+            i32::try_from(index).map_err(|_| ParseError::UnknownUnit(unit.to_string()))?;
+        Ok(number * self.division_factor.powi(exponent))
+    }
+}
+
+/// Inserts `separator` every three digits of the integer part of a formatted number.
+fn group_digits(number: &str, separator: char) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (integer, fraction) = unsigned.split_at(unsigned.find('.').unwrap_or(unsigned.len()));
+
+    let mut grouped = String::with_capacity(number.len() + integer.len() / 3);
+    grouped.push_str(sign);
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    grouped.push_str(fraction);
+    grouped
 }
 
 /// Formats bytes into a human readable string.
 ///
+/// Signed values like `i64` are supported, a negative value is scaled by its magnitude so it gets the same number and
+/// unit as its positive counterpart, which is useful for deltas like freed space. The `-` sign is the only difference
+/// from the positive output, the precision is the same, so `-2048` is `-2.00 KiB` just like `2048` is `2.00 KiB`.
+///
 /// ## Examples
 ///
 /// ```rust,no_run
 /// use handy::human::human_bytes;
 ///
 /// assert_eq!(human_bytes(123_456_789), "118 MiB");
+/// assert_eq!(human_bytes(-2048i64), "-2.00 KiB");
 /// ```
 #[must_use]
 pub fn human_bytes<U>(bytes: U) -> String
@@ -256,6 +560,57 @@ where
     si_humanizer().format_as_parts(bytes)
 }
 
+/// Formats a transfer rate into a human readable string, the rate is formatted like [`human_bytes`] followed by `/s`.
+/// A zero, negative or NaN duration has no meaningful rate so it's formatted as `—/s`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::human::human_bytes_rate;
+///
+/// assert_eq!(human_bytes_rate(2_621_440, 2.0), "1.25 MiB/s");
+/// assert_eq!(human_bytes_rate(1024, 0.0), "—/s");
+/// ```
+///
+/// ## Arguments
+///
+/// * `bytes` - The number of bytes transferred.
+/// * `secs` - The number of seconds it took.
+#[must_use]
+pub fn human_bytes_rate(bytes: u64, secs: f64) -> String {
+    human_rate(binary_humanizer(), bytes, secs)
+}
+
+/// Formats a transfer rate into a human readable string using SI units, like [`human_bytes_rate`].
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::human::human_bytes_rate_si;
+///
+/// assert_eq!(human_bytes_rate_si(2_500_000, 2.0), "1.25 MB/s");
+/// assert_eq!(human_bytes_rate_si(1000, 0.0), "—/s");
+/// ```
+///
+/// ## Arguments
+///
+/// * `bytes` - The number of bytes transferred.
+/// * `secs` - The number of seconds it took.
+#[must_use]
+pub fn human_bytes_rate_si(bytes: u64, secs: f64) -> String {
+    human_rate(si_humanizer(), bytes, secs)
+}
+
+/// Formats `bytes` per `secs` with a humanizer followed by `/s`, or `—/s` if `secs` is not positive.
+#[allow(clippy::cast_precision_loss)]
+fn human_rate(humanizer: &Humanizer, bytes: u64, secs: f64) -> String {
+    if secs.is_nan() || secs <= 0.0 {
+        return "—/s".to_string();
+    }
+
+    format!("{}/s", humanizer.format(bytes as f64 / secs))
+}
+
 /// Formats a number into a human readable string.
 ///
 /// ## Examples
@@ -289,6 +644,74 @@ where
     num_humanizer().format_as_parts(number)
 }
 
+/// Parses a human readable amount of bytes back into bytes, accepting both binary (KiB, MiB, etc.) and SI (KB, MB,
+/// etc.) units.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::human::parse_human_bytes;
+///
+/// assert_eq!(parse_human_bytes("1.5 MiB").unwrap(), 1_572_864);
+/// assert_eq!(parse_human_bytes("1.5 MB").unwrap(), 1_500_000);
+/// ```
+///
+/// ## Errors
+///
+/// - [`ParseError::InvalidNumber`]: If the number could not be parsed or is negative
+/// - [`ParseError::UnknownUnit`]: If the unit is not a byte unit
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn parse_human_bytes(s: &str) -> Result<u64, ParseError> {
+    let bytes = match binary_humanizer().parse(s) {
+        Err(ParseError::UnknownUnit(_)) => si_humanizer().parse(s)?,
+        result => result?,
+    };
+
+    if bytes < 0.0 {
+        return Err(ParseError::InvalidNumber(s.to_string()));
+    }
+
+    Ok(bytes.round() as u64)
+}
+
+/// Parses a human readable number back into a number.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::human::parse_human_number;
+///
+/// assert_eq!(parse_human_number("1.23 M").unwrap(), 1_230_000.0);
+/// ```
+///
+/// ## Errors
+///
+/// - [`ParseError::InvalidNumber`]: If the number could not be parsed
+/// - [`ParseError::UnknownUnit`]: If the unit is not a number unit
+pub fn parse_human_number(s: &str) -> Result<f64, ParseError> {
+    num_humanizer().parse(s)
+}
+
+/// Formats a ratio into a percentage, the ratio is clamped between 0 and 1 and non-finite ratios are formatted as `—`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::human::human_percent;
+///
+/// assert_eq!(human_percent(0.1234, 1), "12.3%");
+/// assert_eq!(human_percent(f64::NAN, 1), "—");
+/// ```
+#[must_use]
+pub fn human_percent(ratio: f64, decimals: usize) -> String {
+    if !ratio.is_finite() {
+        return "—".to_string();
+    }
+
+    let percent = ratio.clamp(0.0, 1.0) * 100.0;
+    format!("{percent:.decimals$}%")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,12 +763,278 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_humanizer_strip_trailing_zeros() {
+        let humanizer = Humanizer::new(&["", "K", "M"]).with_strip_trailing_zeros(true);
+
+        assert_eq!(humanizer.format(0), "0");
+        assert_eq!(humanizer.format(100), "100");
+        assert_eq!(humanizer.format(1_200), "1.2 K");
+        assert_eq!(humanizer.format(2_000), "2 K");
+        assert_eq!(humanizer.format(1_234), "1.23 K");
+        assert_eq!(humanizer.format(20_000), "20 K");
+        assert_eq!(humanizer.format(10_500_000), "10.5 M");
+        assert_eq!(humanizer.format(-1_200), "-1.2 K");
+
+        let humanizer = humanizer.with_strip_trailing_zeros(false);
+        assert_eq!(humanizer.format(1_200), "1.20 K");
+        assert_eq!(humanizer.format(2_000), "2.00 K");
+    }
+
+    #[test]
+    fn test_humanizer_format_into() {
+        let humanizer = Humanizer::new(&["", "K", "M", "B"]);
+        let values = [0, 635, -12_345, 1_234_567, 1_234_567_890];
+
+        let mut buffer = String::new();
+        for value in values {
+            humanizer
+                .format_into(value, &mut buffer)
+                .expect("Failed to format value");
+            buffer.push('|');
+        }
+
+        let expected: String = values
+            .iter()
+            .map(|&value| humanizer.format(value) + "|")
+            .collect();
+        assert_eq!(buffer, expected);
+        assert_eq!(buffer, "0|635|-12.3 K|1.23 M|1.23 B|");
+    }
+
+    #[test]
+    fn test_humanizer_negative() {
+        let humanizer = Humanizer::new(&["", "K", "M", "B"]);
+
+        assert_eq!(humanizer.format(-1), "-1.00");
+        assert_eq!(humanizer.format(-12), "-12.0");
+        assert_eq!(humanizer.format(-999), "-999");
+        assert_eq!(humanizer.format(-1_000), "-1.00 K");
+        assert_eq!(humanizer.format(-12_345), "-12.3 K");
+        assert_eq!(humanizer.format(-1_234_567), "-1.23 M");
+        assert_eq!(humanizer.format(-123_456_789), "-123 M");
+        assert_eq!(humanizer.format(-1_234_567_890), "-1.23 B");
+        assert_eq!(humanizer.format(-0.001), "0.00");
+        assert_eq!(humanizer.format(-0.0), "0");
+        assert_eq!(humanizer.format_as_parts(-1_234_567), (-1.234_567, "M"));
+
+        let humanizer = humanizer.with_strip_trailing_zeros(true);
+        assert_eq!(humanizer.format(-1_000), "-1 K");
+        assert_eq!(humanizer.format(-0.001), "0");
+    }
+
+    #[test]
+    fn test_humanizer_grouping() {
+        let humanizer = Humanizer::new(&["B", "KB"]);
+        assert_eq!(humanizer.format(123_456_789), "123457 KB");
+        assert_eq!(humanizer.format(-123_456_789), "-123457 KB");
+
+        let humanizer = humanizer.with_grouping(Some(','));
+        assert_eq!(humanizer.format(0), "0 B");
+        assert_eq!(humanizer.format(999), "999 B");
+        assert_eq!(humanizer.format(123_456_789), "123,457 KB");
+        assert_eq!(humanizer.format(-123_456_789), "-123,457 KB");
+        assert_eq!(humanizer.format(1_234_567_890_123u64), "1,234,567,890 KB");
+
+        let humanizer = Humanizer::new(&[""])
+            .with_grouping(Some('.'))
+            .with_precision(2);
+        assert_eq!(humanizer.format(1_234_567.891), "1.234.567.89");
+
+        let humanizer = humanizer.with_grouping(None);
+        assert_eq!(humanizer.format(1_234_567.891), "1234567.89");
+    }
+
+    #[test]
+    fn test_humanizer_precision() {
+        let humanizer = Humanizer::new(&["", "K", "M"]).with_precision(3);
+
+        assert_eq!(humanizer.format(0), "0");
+        assert_eq!(humanizer.format(635), "635.000");
+        assert_eq!(humanizer.format(12_345), "12.345 K");
+        assert_eq!(humanizer.format(1_234_567), "1.235 M");
+
+        let humanizer = humanizer.with_precision(0);
+        assert_eq!(humanizer.format(1_234_567), "1 M");
+
+        let humanizer = humanizer.with_precision(2).with_strip_trailing_zeros(true);
+        assert_eq!(humanizer.format(635), "635");
+        assert_eq!(humanizer.format(1_200), "1.2 K");
+    }
+
+    #[test]
+    fn test_humanizer_format_long() {
+        let humanizer = Humanizer::new(&["B", "KB", "MB"])
+            .with_long_units(&["byte", "kilobyte", "megabyte"])
+            .with_pluralization(true)
+            .with_strip_trailing_zeros(true);
+
+        assert_eq!(humanizer.format_long(0), "0 bytes");
+        assert_eq!(humanizer.format_long(1), "1 byte");
+        assert_eq!(humanizer.format_long(2), "2 bytes");
+        assert_eq!(humanizer.format_long(-1), "-1 byte");
+        assert_eq!(humanizer.format_long(1_000), "1 kilobyte");
+        assert_eq!(humanizer.format_long(1_200), "1.2 kilobytes");
+        assert_eq!(humanizer.format_long(2_500_000), "2.5 megabytes");
+        assert_eq!(humanizer.format(1_200), "1.2 KB");
+
+        let humanizer = humanizer.with_pluralization(false);
+        assert_eq!(humanizer.format_long(2), "2 byte");
+
+        let humanizer = Humanizer::new(&["B", "KB"]);
+        assert_eq!(humanizer.format_long(1_200), "1.20 KB");
+    }
+
+    #[test]
+    #[should_panic(expected = "Long units must have the same length as the units")]
+    fn test_humanizer_long_units_length() {
+        let _ = Humanizer::new(&["B", "KB"]).with_long_units(&["byte"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Division factor must be greater than 1, got 0")]
+    fn test_humanizer_division_factor_zero() {
+        let _ = Humanizer::new(&["B", "KB"]).with_division_factor(0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Division factor must be greater than 1, got 1")]
+    fn test_humanizer_division_factor_one() {
+        let _ = Humanizer::new(&["B", "KB"]).with_division_factor(1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Division factor must be greater than 1, got -1024")]
+    fn test_humanizer_division_factor_negative() {
+        let _ = Humanizer::new(&["B", "KB"]).with_division_factor(-1024.0);
+    }
+
     #[test]
     #[should_panic(expected = "Units slice must not be empty")]
     fn test_humanizer_new_empty_units() {
         let _ = Humanizer::new(&[]);
     }
 
+    #[test]
+    fn test_humanizer_parse() {
+        let humanizer = Humanizer::new(&["", "k", "m", "b", "t"]).with_space_before_unit(false);
+
+        assert_eq!(humanizer.parse("0"), Ok(0.0));
+        assert_eq!(humanizer.parse("889"), Ok(889.0));
+        assert_eq!(humanizer.parse("1.5k"), Ok(1_500.0));
+        assert_eq!(humanizer.parse(" 123 m "), Ok(123_000_000.0));
+        assert_eq!(humanizer.parse("2 K"), Ok(2_000.0));
+        assert_eq!(humanizer.parse("-1.5k"), Ok(-1_500.0));
+        assert_eq!(
+            humanizer.parse("1.5 x"),
+            Err(ParseError::UnknownUnit("x".to_string()))
+        );
+        assert_eq!(
+            humanizer.parse("abc"),
+            Err(ParseError::InvalidNumber(String::new()))
+        );
+    }
+
+    #[test]
+    fn test_parse_human_bytes() {
+        assert_eq!(parse_human_bytes("1.5 MiB"), Ok(1_572_864));
+        assert_eq!(parse_human_bytes("1.5 MB"), Ok(1_500_000));
+        assert_eq!(parse_human_bytes("635 B"), Ok(635));
+        assert_eq!(parse_human_bytes("635"), Ok(635));
+        assert_eq!(
+            parse_human_bytes("-1 KiB"),
+            Err(ParseError::InvalidNumber("-1 KiB".to_string()))
+        );
+        assert_eq!(
+            parse_human_bytes("1 K"),
+            Err(ParseError::UnknownUnit("K".to_string()))
+        );
+
+        // formatting the parsed value again gives the same string back
+        for bytes in [
+            0u64,
+            635,
+            12_345,
+            1_234_567,
+            123_456_789,
+            12_345_678_901,
+            123_456_789_012_345,
+        ] {
+            let human = human_bytes(bytes);
+            assert_eq!(human_bytes(parse_human_bytes(&human).unwrap()), human);
+
+            let human = human_bytes_si(bytes);
+            assert_eq!(human_bytes_si(parse_human_bytes(&human).unwrap()), human);
+        }
+    }
+
+    #[test]
+    fn test_parse_human_number() {
+        assert_eq!(parse_human_number("1.23 M"), Ok(1_230_000.0));
+        assert_eq!(parse_human_number("635"), Ok(635.0));
+
+        for number in [
+            0u64,
+            635,
+            12_345,
+            1_234_567,
+            123_456_789,
+            123_456_789_012_345,
+        ] {
+            let human = human_number(number);
+            assert_eq!(human_number(parse_human_number(&human).unwrap()), human);
+        }
+    }
+
+    #[test]
+    fn test_builtin_humanizers() {
+        assert_eq!(Humanizer::binary().format(12_345), human_bytes(12_345));
+        assert_eq!(Humanizer::si().format(12_345), human_bytes_si(12_345));
+        assert_eq!(Humanizer::number().format(12_345), human_number(12_345));
+
+        // the clones can be customized without affecting the shared ones
+        let humanizer = Humanizer::binary().clone().with_strip_trailing_zeros(true);
+        assert_eq!(humanizer.format(2_048), "2 KiB");
+        assert_eq!(human_bytes(2_048), "2.00 KiB");
+    }
+
+    #[test]
+    fn test_human_bytes_rate() {
+        assert_eq!(human_bytes_rate(0, 1.0), "0 B/s");
+        assert_eq!(human_bytes_rate(512, 1.0), "512 B/s");
+        assert_eq!(human_bytes_rate(1_258_291, 1.0), "1.20 MiB/s");
+        assert_eq!(human_bytes_rate(2_621_440, 2.0), "1.25 MiB/s");
+        assert_eq!(human_bytes_rate(1024, 0.5), "2.00 KiB/s");
+        assert_eq!(
+            human_bytes_rate(10 * 1024 * 1024 * 1024, 10.0),
+            "1.00 GiB/s"
+        );
+        assert_eq!(human_bytes_rate(1024, f64::INFINITY), "0 B/s");
+
+        assert_eq!(human_bytes_rate_si(2_500_000, 2.0), "1.25 MB/s");
+        assert_eq!(human_bytes_rate_si(123_000, 1.0), "123 KB/s");
+
+        for secs in [0.0, -0.0, -1.0, f64::NAN, f64::NEG_INFINITY] {
+            assert_eq!(human_bytes_rate(1024, secs), "—/s");
+            assert_eq!(human_bytes_rate_si(1000, secs), "—/s");
+        }
+    }
+
+    #[test]
+    fn test_human_percent() {
+        assert_eq!(human_percent(0.0, 1), "0.0%");
+        assert_eq!(human_percent(1.0, 1), "100.0%");
+        assert_eq!(human_percent(0.5, 1), "50.0%");
+        assert_eq!(human_percent(0.1234, 1), "12.3%");
+        assert_eq!(human_percent(0.1234, 0), "12%");
+        assert_eq!(human_percent(0.1234, 3), "12.340%");
+        assert_eq!(human_percent(1.5, 1), "100.0%");
+        assert_eq!(human_percent(-0.2, 1), "0.0%");
+        assert_eq!(human_percent(f64::NAN, 1), "—");
+        assert_eq!(human_percent(f64::INFINITY, 1), "—");
+        assert_eq!(human_percent(f64::NEG_INFINITY, 1), "—");
+    }
+
     #[test]
     fn test_human_bytes() {
         assert_eq!(human_bytes(0), "0 B");
@@ -359,6 +1048,30 @@ mod tests {
         assert_eq!(human_bytes(123_456_789_012_345_678u64), "110 PiB");
     }
 
+    #[test]
+    fn test_human_bytes_signed() {
+        assert_eq!(human_bytes(0i64), "0 B");
+        assert_eq!(human_bytes(-1i64), "-1.00 B");
+        assert_eq!(human_bytes(-635i64), "-635 B");
+        assert_eq!(human_bytes(-1023i64), "-1023 B");
+        assert_eq!(human_bytes(-2048i64), "-2.00 KiB");
+        assert_eq!(human_bytes(-12_345i64), "-12.1 KiB");
+        assert_eq!(human_bytes(-1_234_567i64), "-1.18 MiB");
+        assert_eq!(human_bytes(-123_456_789i64), "-118 MiB");
+        assert_eq!(human_bytes(-12_345_678_901i64), "-11.5 GiB");
+        assert_eq!(human_bytes(i64::MIN), "-8.00 EiB");
+        assert_eq!(human_bytes(-5i8), "-5.00 B");
+
+        assert_eq!(human_bytes_si(-2048i64), "-2.05 KB");
+        assert_eq!(human_bytes_si(-123_456_789i64), "-123 MB");
+        assert_eq!(human_bytes_as_parts(-2048i64), (-2.0, "KiB"));
+        assert_eq!(human_number(-1_500i32), "-1.50 K");
+
+        for value in [1i64, 635, 2048, 12_345, 1_234_567, 123_456_789] {
+            assert_eq!(human_bytes(-value), format!("-{}", human_bytes(value)));
+        }
+    }
+
     #[test]
     fn test_human_bytes_as_parts() {
         assert_eq!(human_bytes_as_parts(0), (0.0, "B"));
@@ -1,4 +1,8 @@
-use std::fmt::Display;
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+    hash::Hash,
+};
 
 /// Trait to convert a vector of `T` into a vector of `&T` or `&mut T`
 pub trait IntoRefVec<'a, T> {
@@ -27,6 +31,27 @@ pub trait IntoRefVec<'a, T> {
     /// assert_eq!(y, vec![&1, &2, &3]);
     /// ```
     fn as_ref_vec(&'a self) -> Vec<&'a T>;
+
+    /// Splits the items into non-overlapping chunks of `&T` with `size` items each, the last chunk may be shorter.
+    ///
+    /// Returns an empty vector if `size` is `0`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::iter::IntoRefVec;
+    ///
+    /// let x = vec![1, 2, 3, 4, 5];
+    /// let y = x.chunks_ref(2);
+    /// assert_eq!(y, vec![vec![&1, &2], vec![&3, &4], vec![&5]]);
+    /// ```
+    fn chunks_ref(&'a self, size: usize) -> Vec<Vec<&'a T>> {
+        if size == 0 {
+            return Vec::new();
+        }
+
+        self.as_ref_vec().chunks(size).map(<[&T]>::to_vec).collect()
+    }
 }
 
 impl<'a, T> IntoRefVec<'a, T> for Option<Vec<T>> {
@@ -59,6 +84,95 @@ impl<'a, T> IntoRefVec<'a, T> for [T] {
     }
 }
 
+impl<'a, T, const N: usize> IntoRefVec<'a, T> for [T; N] {
+    fn as_mut_ref_vec(&'a mut self) -> Vec<&'a mut T> {
+        self[..].as_mut_ref_vec()
+    }
+
+    fn as_ref_vec(&'a self) -> Vec<&'a T> {
+        self[..].as_ref_vec()
+    }
+}
+
+/// Trait to group consecutive items of a vector into vectors of `&T`, implemented for everything that implements
+/// [`IntoRefVec`]
+pub trait GroupAdjacent<'a, T>: IntoRefVec<'a, T> {
+    /// Groups consecutive items that have the same key, items with the same key that aren't next to each other end up
+    /// in different groups.
+    ///
+    /// ## Arguments
+    ///
+    /// * `key` - The function that returns the key of an item
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::iter::GroupAdjacent;
+    ///
+    /// let x = vec![1, 1, 2, 2, 2, 1];
+    /// let y = x.group_adjacent_by(|n| *n);
+    /// assert_eq!(y, vec![vec![&1, &1], vec![&2, &2, &2], vec![&1]]);
+    /// ```
+    fn group_adjacent_by<K, F>(&'a self, key: F) -> Vec<Vec<&'a T>>
+    where
+        K: PartialEq,
+        F: Fn(&T) -> K,
+    {
+        let mut groups: Vec<Vec<&'a T>> = Vec::new();
+        let mut last_key: Option<K> = None;
+
+        for item in self.as_ref_vec() {
+            let item_key = key(item);
+            match groups.last_mut() {
+                Some(group) if last_key.as_ref() == Some(&item_key) => group.push(item),
+                _ => groups.push(vec![item]),
+            }
+            last_key = Some(item_key);
+        }
+
+        groups
+    }
+}
+
+impl<'a, T, C> GroupAdjacent<'a, T> for C where C: IntoRefVec<'a, T> + ?Sized {}
+
+/// Trait to get the distinct items of a vector as a vector of `&T`
+pub trait Unique<T> {
+    /// Returns the first occurrence of each distinct item, keeping their order
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::iter::Unique;
+    ///
+    /// let x = vec![1, 2, 2, 3, 1];
+    /// let y = x.unique();
+    /// assert_eq!(y, vec![&1, &2, &3]);
+    /// ```
+    fn unique(&self) -> Vec<&T>
+    where
+        T: Eq + Hash;
+}
+
+impl<T> Unique<T> for [T] {
+    fn unique(&self) -> Vec<&T>
+    where
+        T: Eq + Hash,
+    {
+        let mut seen = HashSet::with_capacity(self.len());
+        self.iter().filter(|item| seen.insert(*item)).collect()
+    }
+}
+
+impl<T> Unique<T> for Vec<T> {
+    fn unique(&self) -> Vec<&T>
+    where
+        T: Eq + Hash,
+    {
+        self.as_slice().unique()
+    }
+}
+
 /// Trait for converting an iterable of items that can be displayed into a vector of strings
 pub trait StringIterable {
     /// Converts the iterable to a vector of strings.
@@ -73,6 +187,59 @@ pub trait StringIterable {
     /// assert_eq!(y, vec!["1", "2", "3"]);
     /// ```
     fn to_string_vec(&self) -> Vec<String>;
+
+    /// Converts the items to strings and joins them with a separator, without collecting them into a vector first.
+    ///
+    /// ## Arguments
+    ///
+    /// * `sep` - The separator to put between the items
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use handy::iter::StringIterable;
+    ///
+    /// let x = vec![1, 2, 3];
+    /// assert_eq!(x.join("-"), "1-2-3");
+    /// ```
+    fn join(&self, sep: &str) -> String;
+}
+
+/// Converts the items of any iterable that can be displayed into a vector of strings, for the types that don't
+/// implement [`StringIterable`] like iterators and sets.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use handy::iter::to_string_vec_iter;
+/// use std::collections::BTreeSet;
+///
+/// let x = BTreeSet::from([3, 1, 2]);
+/// assert_eq!(to_string_vec_iter(&x), vec!["1", "2", "3"]);
+/// assert_eq!(to_string_vec_iter((1..4).map(|n| n * 2)), vec!["2", "4", "6"]);
+/// ```
+pub fn to_string_vec_iter<I>(iter: I) -> Vec<String>
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    iter.into_iter().map(|item| item.to_string()).collect()
+}
+
+/// Writes each item followed by `sep` except for the last one
+fn join_display<'a, T, I>(items: I, sep: &str) -> String
+where
+    T: Display + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let mut joined = String::new();
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            joined.push_str(sep);
+        }
+        let _ = write!(joined, "{item}");
+    }
+    joined
 }
 
 impl<T> StringIterable for Option<Vec<T>>
@@ -84,6 +251,10 @@ where
             .flat_map(|v| v.iter().map(ToString::to_string))
             .collect()
     }
+
+    fn join(&self, sep: &str) -> String {
+        join_display(self.iter().flatten(), sep)
+    }
 }
 
 impl<T> StringIterable for [T]
@@ -93,6 +264,10 @@ where
     fn to_string_vec(&self) -> Vec<String> {
         self.iter().map(ToString::to_string).collect()
     }
+
+    fn join(&self, sep: &str) -> String {
+        join_display(self, sep)
+    }
 }
 
 impl<T> StringIterable for Vec<T>
@@ -102,11 +277,16 @@ where
     fn to_string_vec(&self) -> Vec<String> {
         self.iter().map(ToString::to_string).collect()
     }
+
+    fn join(&self, sep: &str) -> String {
+        join_display(self, sep)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeSet;
 
     #[test]
     fn test_into_ref_vec() {
@@ -129,6 +309,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_into_ref_vec_array() {
+        let a = [1, 2, 3, 4, 5];
+        assert_eq!(a.as_ref_vec(), vec![&1, &2, &3, &4, &5]);
+        assert_eq!(a.chunks_ref(2), vec![vec![&1, &2], vec![&3, &4], vec![&5]]);
+
+        let mut a2: [i32; 5] = [1, 2, 3, 4, 5];
+        for n in a2.as_mut_ref_vec() {
+            *n *= 2;
+        }
+        assert_eq!(a2, [2, 4, 6, 8, 10]);
+
+        let a3: [i32; 0] = [];
+        assert!(a3.as_ref_vec().is_empty());
+    }
+
+    #[test]
+    fn test_chunks_ref() {
+        let v = vec![1, 2, 3, 4, 5, 6];
+        assert_eq!(
+            v.chunks_ref(2),
+            vec![vec![&1, &2], vec![&3, &4], vec![&5, &6]]
+        );
+        assert_eq!(v.chunks_ref(4), vec![vec![&1, &2, &3, &4], vec![&5, &6]]);
+        assert_eq!(v.chunks_ref(10), vec![vec![&1, &2, &3, &4, &5, &6]]);
+        assert!(v.chunks_ref(0).is_empty());
+
+        let v2 = [1, 2, 3];
+        assert_eq!(v2[..].chunks_ref(2), vec![vec![&1, &2], vec![&3]]);
+
+        let v3 = Some(v);
+        assert_eq!(v3.chunks_ref(3), vec![vec![&1, &2, &3], vec![&4, &5, &6]]);
+
+        let v4: Vec<i32> = Vec::new();
+        assert!(v4.chunks_ref(2).is_empty());
+    }
+
+    #[test]
+    fn test_group_adjacent_by() {
+        let v = vec![1, 1, 2, 2, 2, 1];
+        assert_eq!(
+            v.group_adjacent_by(|n| *n),
+            vec![vec![&1, &1], vec![&2, &2, &2], vec![&1]]
+        );
+        assert_eq!(
+            v.group_adjacent_by(|n| n % 2 == 0),
+            vec![vec![&1, &1], vec![&2, &2, &2], vec![&1]]
+        );
+        assert_eq!(v.group_adjacent_by(|_| ()), vec![v.as_ref_vec()]);
+
+        let v2 = ["a", "ab", "b", "abc"];
+        assert_eq!(
+            v2[..].group_adjacent_by(|s| s.len()),
+            vec![vec![&"a"], vec![&"ab"], vec![&"b"], vec![&"abc"]]
+        );
+
+        let v3 = Some(v);
+        assert_eq!(v3.group_adjacent_by(|n| *n).len(), 3);
+
+        let v4: Vec<i32> = Vec::new();
+        assert!(v4.group_adjacent_by(|n| *n).is_empty());
+    }
+
+    #[test]
+    fn test_unique() {
+        assert_eq!([1, 2, 2, 3, 1].unique(), vec![&1, &2, &3]);
+
+        let v = vec!["b", "a", "b", "c", "a"];
+        assert_eq!(v.unique(), vec![&"b", &"a", &"c"]);
+
+        let v2 = vec![1, 2, 3];
+        assert_eq!(v2.unique(), v2.as_ref_vec());
+
+        let v3: Vec<i32> = Vec::new();
+        assert!(v3.unique().is_empty());
+    }
+
     #[test]
     fn test_string_iterable() {
         let v = vec![1, 2, 3, 4, 5];
@@ -143,4 +400,38 @@ mod tests {
         let v4: Option<Vec<i32>> = None;
         assert_eq!(v4.to_string_vec(), Vec::<String>::new());
     }
+
+    #[test]
+    fn test_to_string_vec_iter() {
+        let set = BTreeSet::from([3, 1, 2, 1]);
+        assert_eq!(to_string_vec_iter(&set), vec!["1", "2", "3"]);
+
+        let mut hash_set = to_string_vec_iter(HashSet::from(["b", "a"]));
+        hash_set.sort();
+        assert_eq!(hash_set, vec!["a", "b"]);
+
+        assert_eq!(
+            to_string_vec_iter((1..4).map(|n| n * 2)),
+            vec!["2", "4", "6"]
+        );
+        assert!(to_string_vec_iter(Vec::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_string_iterable_join() {
+        let v = vec![1, 2, 3];
+        assert_eq!(v.join("-"), "1-2-3");
+        assert_eq!(v.join(""), "123");
+
+        assert_eq!([1, 2, 3].join("-"), "1-2-3");
+
+        let v3 = Some(v);
+        assert_eq!(v3.join(", "), "1, 2, 3");
+
+        let v4: Option<Vec<i32>> = None;
+        assert_eq!(v4.join("-"), "");
+
+        let v5: Vec<f64> = vec![1.5];
+        assert_eq!(v5.join("-"), "1.5");
+    }
 }
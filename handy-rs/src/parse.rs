@@ -1,7 +1,10 @@
 use crate::errors::ParseError;
+#[cfg(feature = "human")]
+use crate::human::Humanizer;
 use std::str::FromStr;
 
-/// Splits a string into a number and a suffix, e.g. `123abc` -> (123, "abc").
+/// Splits a string into a number and a suffix, e.g. `123abc` -> (123, "abc"). Leading whitespace is skipped and the
+/// number can start with a `+` or `-` sign.
 ///
 /// ## Examples
 ///
@@ -9,6 +12,7 @@ use std::str::FromStr;
 /// use handy::parse::split_at_non_digits;
 ///
 /// assert_eq!(split_at_non_digits("123abc").unwrap(), (123, "abc".to_string()));
+/// assert_eq!(split_at_non_digits(" -5m").unwrap(), (-5, "m".to_string()));
 /// ```
 ///
 /// ## Errors
@@ -18,9 +22,119 @@ pub fn split_at_non_digits<N>(s: &str) -> Result<(N, String), ParseError>
 where
     N: FromStr,
 {
-    let split_index = s
+    let (prefix, suffix_str) = split_number(s)?;
+    Ok((prefix, suffix_str.to_string()))
+}
+
+/// Splits a string into all of its numbers and the suffixes after them, e.g. `1h30m` -> [(1, "h"), (30, "m")]. Each
+/// suffix ends at the next digit, or at the `+` or `-` sign right before it, and the last one may be empty.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::parse::split_all_numbers;
+///
+/// assert_eq!(
+///     split_all_numbers("1h30m").unwrap(),
+///     vec![(1, "h".to_string()), (30, "m".to_string())]
+/// );
+/// ```
+///
+/// ## Errors
+///
+/// - [`ParseError::InvalidNumber`]: If one of the numbers cannot be parsed
+pub fn split_all_numbers<N>(s: &str) -> Result<Vec<(N, String)>, ParseError>
+where
+    N: FromStr,
+{
+    let mut numbers = Vec::new();
+    let mut rest = s;
+
+    while !rest.trim().is_empty() {
+        let (number, suffix) = split_number(rest)?;
+        let suffix_len = suffix
+            .char_indices()
+            .find(|&(i, c)| {
+                c.is_ascii_digit()
+                    || (matches!(c, '+' | '-')
+                        && suffix[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+            })
+            .map_or(suffix.len(), |(i, _)| i);
+
+        numbers.push((number, suffix[..suffix_len].to_string()));
+        rest = &suffix[suffix_len..];
+    }
+
+    Ok(numbers)
+}
+
+/// Parses an integer in the radix given by its prefix: `0x` for hexadecimal, `0b` for binary, `0o` for octal and
+/// decimal otherwise. Leading whitespace, a `+` or `-` sign and a unit suffix after the number are allowed, like in
+/// [`split_at_non_digits`].
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::parse::parse_int_auto;
+///
+/// assert_eq!(parse_int_auto("0xFF").unwrap(), 255);
+/// assert_eq!(parse_int_auto("-0b1010").unwrap(), -10);
+/// assert_eq!(parse_int_auto("42ms").unwrap(), 42);
+/// ```
+///
+/// ## Errors
+///
+/// - [`ParseError::InvalidNumber`]: If the number cannot be parsed or has a digit its radix doesn't allow, like `0b12`
+pub fn parse_int_auto(s: &str) -> Result<i64, ParseError> {
+    let s = s.trim_start();
+    let (negative, unsigned) = match s.strip_prefix(['+', '-']) {
+        Some(rest) => (s.starts_with('-'), rest),
+        None => (false, s),
+    };
+
+    let prefix = unsigned.get(..2).map(str::to_ascii_lowercase);
+    let (radix, digits) = match prefix.as_deref() {
+        Some("0x") => (16, &unsigned[2..]),
+        Some("0b") => (2, &unsigned[2..]),
+        Some("0o") => (8, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+
+    let digits_len = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    let number_str = &s[..s.len() - digits.len() + digits_len];
+
+    // a digit the radix doesn't allow is a malformed number rather than the start of a suffix, e.g. `0b102`
+    if radix != 10 && digits[digits_len..].starts_with(|c: char| c.is_ascii_digit()) {
+        let malformed_len = digits[digits_len..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(digits.len(), |i| digits_len + i);
+        return Err(ParseError::InvalidNumber(
+            s[..s.len() - digits.len() + malformed_len].to_string(),
+        ));
+    }
+
+    // parsing the sign with the digits keeps i64::MIN in range
+    let signed = format!(
+        "{}{}",
+        if negative { "-" } else { "" },
+        &digits[..digits_len]
+    );
+    i64::from_str_radix(&signed, radix)
+        .map_err(|_| ParseError::InvalidNumber(number_str.to_string()))
+}
+
+/// Splits the number at the start of `s` from the rest of the string, see [`split_at_non_digits`]
+fn split_number<N>(s: &str) -> Result<(N, &str), ParseError>
+where
+    N: FromStr,
+{
+    let s = s.trim_start();
+    let sign_len = usize::from(s.starts_with(['+', '-']));
+    let split_index = s[sign_len..]
         .find(|c: char| !c.is_ascii_digit() && c != '.')
-        .unwrap_or(s.len());
+        .map_or(s.len(), |i| i + sign_len);
 
     let (prefix_str, suffix_str) = s.split_at(split_index);
 
@@ -28,12 +142,49 @@ where
         .parse()
         .map_err(|_| ParseError::InvalidNumber(prefix_str.to_string()))?;
 
-    Ok((prefix, suffix_str.to_string()))
+    Ok((prefix, suffix_str))
+}
+
+/// Parses a number with a unit, scaling it by the unit's position in the humanizer's units, e.g. `2 KiB` -> 2048.
+///
+/// Requires the `human` feature.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::{human::Humanizer, parse::parse_with_unit};
+///
+/// assert_eq!(parse_with_unit::<u64>("2 KiB", Humanizer::binary()).unwrap(), 2048);
+/// assert_eq!(parse_with_unit::<f64>("1.5k", Humanizer::number()).unwrap(), 1500.0);
+/// ```
+///
+/// ## Errors
+///
+/// - [`ParseError::InvalidNumber`]: If the number cannot be parsed or the scaled number doesn't fit in `N`, e.g. a
+///   fraction for an integer type
+/// - [`ParseError::UnknownUnit`]: If the unit is not one of the humanizer's units
+#[cfg(feature = "human")]
+pub fn parse_with_unit<N>(s: &str, humanizer: &Humanizer) -> Result<N, ParseError>
+where
+    N: FromStr,
+{
+    let mut scaled = humanizer.parse(s)?;
+
+    // scaling in floating point can leave exact decimals slightly off, e.g. 1.001 * 1000 = 1000.9999999999999
+    let rounded = scaled.round();
+    if (scaled - rounded).abs() <= rounded.abs() * 1e-12 {
+        scaled = rounded;
+    }
+
+    let scaled = scaled.to_string();
+    scaled
+        .parse()
+        .map_err(|_| ParseError::InvalidNumber(scaled))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::split_at_non_digits;
+    use super::*;
 
     #[allow(clippy::approx_constant)]
     #[test]
@@ -54,5 +205,127 @@ mod tests {
             split_at_non_digits("3.14159").unwrap(),
             (3.14159, String::new())
         );
+        assert_eq!(split_at_non_digits("-5m").unwrap(), (-5, "m".to_string()));
+        assert_eq!(split_at_non_digits(" -5m").unwrap(), (-5, "m".to_string()));
+        assert_eq!(
+            split_at_non_digits("+3.5kg").unwrap(),
+            (3.5, "kg".to_string())
+        );
+        assert_eq!(
+            split_at_non_digits("  42 units").unwrap(),
+            (42, " units".to_string())
+        );
+        assert_eq!(
+            split_at_non_digits::<i32>("-m"),
+            Err(ParseError::InvalidNumber("-".to_string()))
+        );
+        assert_eq!(
+            split_at_non_digits::<u32>("-5m"),
+            Err(ParseError::InvalidNumber("-5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_split_all_numbers() {
+        assert_eq!(
+            split_all_numbers("1h30m").unwrap(),
+            vec![(1, "h".to_string()), (30, "m".to_string())]
+        );
+        assert_eq!(
+            split_all_numbers("2d 4h 15").unwrap(),
+            vec![
+                (2, "d ".to_string()),
+                (4, "h ".to_string()),
+                (15, String::new())
+            ]
+        );
+        assert_eq!(
+            split_all_numbers("1.5kg2.25g").unwrap(),
+            vec![(1.5, "kg".to_string()), (2.25, "g".to_string())]
+        );
+        assert_eq!(
+            split_all_numbers("1h-30m").unwrap(),
+            vec![(1, "h".to_string()), (-30, "m".to_string())]
+        );
+        assert_eq!(
+            split_all_numbers("5 - 3+2").unwrap(),
+            vec![
+                (5, " - ".to_string()),
+                (3, String::new()),
+                (2, String::new())
+            ]
+        );
+        assert_eq!(split_all_numbers::<i32>("").unwrap(), vec![]);
+        assert_eq!(
+            split_all_numbers::<i32>("h30m"),
+            Err(ParseError::InvalidNumber(String::new()))
+        );
+    }
+
+    #[test]
+    fn test_parse_int_auto() {
+        assert_eq!(parse_int_auto("0xFF"), Ok(255));
+        assert_eq!(parse_int_auto("0Xff"), Ok(255));
+        assert_eq!(parse_int_auto("0b1010"), Ok(10));
+        assert_eq!(parse_int_auto("0o17"), Ok(15));
+        assert_eq!(parse_int_auto("42"), Ok(42));
+        assert_eq!(parse_int_auto(" -42"), Ok(-42));
+        assert_eq!(parse_int_auto("+0x10"), Ok(16));
+        assert_eq!(parse_int_auto("-0x8000000000000000"), Ok(i64::MIN));
+        assert_eq!(
+            parse_int_auto("0b102"),
+            Err(ParseError::InvalidNumber("0b102".to_string()))
+        );
+        assert_eq!(
+            parse_int_auto("-0o78ms"),
+            Err(ParseError::InvalidNumber("-0o78".to_string()))
+        );
+        assert_eq!(parse_int_auto("0b101kb"), Ok(5));
+        assert_eq!(parse_int_auto("100ms"), Ok(100));
+        assert_eq!(parse_int_auto("0"), Ok(0));
+        assert_eq!(
+            parse_int_auto("0x"),
+            Err(ParseError::InvalidNumber("0x".to_string()))
+        );
+        assert_eq!(
+            parse_int_auto("abc"),
+            Err(ParseError::InvalidNumber(String::new()))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "human")]
+    fn test_parse_with_unit() {
+        let binary = Humanizer::binary();
+        assert_eq!(parse_with_unit::<u64>("2 KiB", binary), Ok(2048));
+        assert_eq!(parse_with_unit::<u64>("2KiB", binary), Ok(2048));
+        assert_eq!(parse_with_unit::<u32>("1.5 MiB", binary), Ok(1_572_864));
+        assert_eq!(parse_with_unit::<f64>("0.5 KiB", binary), Ok(512.0));
+        assert_eq!(parse_with_unit::<i64>("-1 KiB", binary), Ok(-1024));
+        assert_eq!(
+            parse_with_unit::<u64>("1.1 B", binary),
+            Err(ParseError::InvalidNumber("1.1".to_string()))
+        );
+        assert_eq!(
+            parse_with_unit::<u8>("1 KiB", binary),
+            Err(ParseError::InvalidNumber("1024".to_string()))
+        );
+        assert_eq!(
+            parse_with_unit::<u64>("2 KB", binary),
+            Err(ParseError::UnknownUnit("KB".to_string()))
+        );
+
+        let number = Humanizer::number();
+        assert_eq!(parse_with_unit::<u64>("1.001 K", number), Ok(1001));
+        for i in 0..1000 {
+            assert_eq!(
+                parse_with_unit::<u64>(&format!("1.{i:03} K"), number),
+                Ok(1000 + i)
+            );
+            assert_eq!(
+                parse_with_unit::<u64>(&format!("3.{i:03} MB"), Humanizer::si()),
+                Ok(3_000_000 + i * 1000)
+            );
+        }
     }
 }
@@ -1,29 +1,76 @@
+use crate::errors::PatternError;
 use jaro_winkler::jaro_winkler;
 use levenshtein::levenshtein;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use regex::Regex;
 use std::path::Path;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
 
 /// The margin of error for string similarity scores.
 pub const ERROR_MARGIN: f64 = 0.001;
 
 /// Converts a glob pattern to a regex pattern.
 ///
+/// Supports `*` (any characters except `/`), `**` (any characters including `/`, where `**/` also matches no
+/// directories), `?` (any single character except `/`) and character classes like `[abc]`, `[a-z]` and `[!a-z]`, a `[` without a matching `]` is
+/// treated as a literal.
+///
 /// ## Examples
 ///
 /// ```rust,no_run
 /// use handy::pattern::glob_to_regex_pattern;
 ///
-/// assert_eq!(glob_to_regex_pattern("fish*.txt"), "fish.*\\.txt");
+/// assert_eq!(glob_to_regex_pattern("fish*.txt"), "fish[^/]*\\.txt");
+/// assert_eq!(glob_to_regex_pattern("src/**/*.rs"), "src/(?:.*/)?[^/]*\\.rs");
+/// assert_eq!(glob_to_regex_pattern("file[!0-9].txt"), "file[^0-9]\\.txt");
 /// ```
 #[must_use]
 pub fn glob_to_regex_pattern(pattern: &str) -> String {
     let mut regex_pattern = String::new();
     let mut escaping = false;
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+
+        // Match any character in the class
+        let class_end = if c == '[' && !escaping {
+            glob_class_end(&chars, i)
+        } else {
+            None
+        };
+        if let Some(end) = class_end {
+            regex_pattern.push('[');
+            if matches!(chars[i], '!' | '^') {
+                regex_pattern.push('^');
+                i += 1;
+            }
+            for &class_c in &chars[i..end] {
+                if matches!(class_c, '[' | ']' | '\\' | '&' | '~') {
+                    regex_pattern.push('\\');
+                }
+                regex_pattern.push(class_c);
+            }
+            regex_pattern.push(']');
+            i = end + 1;
+            continue;
+        }
 
-    for c in pattern.chars() {
         match c {
-            '*' if !escaping => regex_pattern.push_str(".*"), // Match any sequence of characters
-            '?' if !escaping => regex_pattern.push('.'),      // Match any single character
+            '*' if !escaping && chars.get(i) == Some(&'*') => {
+                // Match any sequence of characters across directories, including no directories for `**/`
+                i += 1;
+                if chars.get(i) == Some(&'/') {
+                    regex_pattern.push_str("(?:.*/)?");
+                    i += 1;
+                } else {
+                    regex_pattern.push_str(".*");
+                }
+            }
+            '*' if !escaping => regex_pattern.push_str("[^/]*"), // Match any sequence of characters in a directory
+            '?' if !escaping => regex_pattern.push_str("[^/]"), // Match any single character in a directory
             '.' | '+' | '(' | ')' | '|' | '^' | '$' | '[' | ']' | '{' | '}' | '\\' if !escaping => {
                 regex_pattern.push('\\'); // Escape regex special characters
                 regex_pattern.push(c);
@@ -38,6 +85,23 @@ pub fn glob_to_regex_pattern(pattern: &str) -> String {
     regex_pattern
 }
 
+/// Finds the index of the `]` closing a glob character class whose contents start at `start`, a `]` right at the start
+/// (after the optional `!` or `^`) is part of the class.
+fn glob_class_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut i = start;
+    if matches!(chars.get(i), Some('!' | '^')) {
+        i += 1;
+    }
+    if chars.get(i) == Some(&']') {
+        i += 1;
+    }
+
+    chars[i.min(chars.len())..]
+        .iter()
+        .position(|&c| c == ']')
+        .map(|pos| i + pos)
+}
+
 /// Checks if a string similarity score is close to the upper bound (1.0), which (according to the [`ERROR_MARGIN`]) indicates a perfect match.
 ///
 /// ## Arguments
@@ -52,7 +116,69 @@ pub fn is_close_to_upper_bound(score: f64) -> bool {
     (score - 1.0).abs() < ERROR_MARGIN
 }
 
-/// Checks if a path's filename matches a glob pattern.
+/// A glob pattern compiled once so it can be matched against many paths, see [`glob_to_regex_pattern`] for the syntax.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use handy::pattern::GlobMatcher;
+///
+/// let matcher = GlobMatcher::new("f*.txt").unwrap();
+/// assert!(matcher.is_match(Path::new("/path/to/fish.txt")));
+/// assert!(matcher.is_match_str("fish.txt"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GlobMatcher {
+    pattern: String,
+    regex: Regex,
+}
+
+impl GlobMatcher {
+    /// Compiles a glob pattern
+    ///
+    /// ## Arguments
+    ///
+    /// * `pattern` - The glob pattern
+    ///
+    /// ## Errors
+    ///
+    /// - [`PatternError::InvalidGlob`]: If the pattern can't be compiled
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let regex = Regex::new(&format!("^{}$", glob_to_regex_pattern(pattern)))
+            .map_err(|e| PatternError::InvalidGlob(pattern.to_string(), e.to_string()))?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex,
+        })
+    }
+
+    /// Returns the glob pattern
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Checks if a path's filename matches the pattern, always `false` for paths without a filename
+    #[must_use]
+    pub fn is_match(&self, path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| self.is_match_str(&name.to_string_lossy()))
+    }
+
+    /// Checks if a string matches the pattern
+    #[must_use]
+    pub fn is_match_str(&self, s: &str) -> bool {
+        self.regex.is_match(s)
+    }
+}
+
+/// Checks if a path's filename matches a glob pattern, use a [`GlobMatcher`] to match many paths against the same
+/// pattern. An invalid pattern matches nothing, like in [`match_path_with_glob_pattern`].
+///
+/// **Breaking change in 3.0.0**: the whole filename has to match the pattern, before it was enough for part of it to
+/// match (`*.txt` matched `a.txt.bak`) and an invalid pattern matched every filename.
 ///
 /// ## Examples
 ///
@@ -62,22 +188,27 @@ pub fn is_close_to_upper_bound(score: f64) -> bool {
 ///
 /// assert!(match_filename_with_glob_pattern(Path::new("fish.txt"), "f*.txt"));
 /// ```
-///
-/// ## Panics
-///
-/// This function panics if the internal glob pattern `.*` is invalid.
 #[must_use]
 pub fn match_filename_with_glob_pattern(path: &Path, pattern: &str) -> bool {
-    let regex_pattern = glob_to_regex_pattern(pattern);
-    let re = Regex::new(&regex_pattern).unwrap_or(Regex::new(".*").unwrap());
-
-    if let Some(name) = path.file_name().map(|s| s.to_string_lossy().to_string()) {
-        if re.is_match(&name) {
-            return true;
-        }
-    }
+    GlobMatcher::new(pattern).is_ok_and(|matcher| matcher.is_match(path))
+}
 
-    false
+/// Checks if a whole path matches a glob pattern, see [`glob_to_regex_pattern`] for the syntax. An invalid pattern
+/// matches nothing.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use handy::pattern::match_path_with_glob_pattern;
+///
+/// assert!(match_path_with_glob_pattern(Path::new("src/a/b/c.rs"), "src/**/*.rs"));
+/// assert!(!match_path_with_glob_pattern(Path::new("src/a/b/c.rs"), "src/*.rs"));
+/// ```
+#[must_use]
+pub fn match_path_with_glob_pattern(path: &Path, pattern: &str) -> bool {
+    let regex_pattern = format!("^{}$", glob_to_regex_pattern(pattern));
+    Regex::new(&regex_pattern).is_ok_and(|re| re.is_match(&path.to_string_lossy()))
 }
 
 /// Returns a similarity score between two strings using a fuzzy matching algorithm.
@@ -101,19 +232,54 @@ pub fn match_filename_with_glob_pattern(path: &Path, pattern: &str) -> bool {
 ///
 /// ## Returns
 ///
-/// The similarity score between the two strings.
-#[allow(clippy::cast_precision_loss)]
+/// The similarity score between the two strings, `1.0` if both are empty and `0.0` if only one of them is.
 #[must_use]
 pub fn match_string(s1: &str, s2: &str) -> f64 {
+    match_string_with(s1, s2, levenshtein)
+}
+
+/// Returns a similarity score between two strings like [`match_string`] but using the Damerau-Levenshtein distance,
+/// so swapping two adjacent characters (e.g. `teh` and `the`) counts as one edit instead of two.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::pattern::{match_string, match_string_damerau};
+///
+/// assert!(match_string_damerau("teh", "the") > match_string("teh", "the"));
+/// ```
+///
+/// ## Arguments
+///
+/// * `s1` - The first string.
+/// * `s2` - The second string.
+///
+/// ## Returns
+///
+/// The similarity score between the two strings, `1.0` if both are empty and `0.0` if only one of them is.
+#[must_use]
+pub fn match_string_damerau(s1: &str, s2: &str) -> f64 {
+    match_string_with(s1, s2, damerau_levenshtein)
+}
+
+/// Returns the similarity score when at least one of the strings is empty: two empty strings are identical but an empty
+/// string doesn't match anything else, even though it's technically a substring of everything.
+fn empty_similarity(s1: &str, s2: &str) -> f64 {
+    if s1.is_empty() && s2.is_empty() {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Returns a similarity score between two strings based on the given edit distance function.
+#[allow(clippy::cast_precision_loss)]
+fn match_string_with(s1: &str, s2: &str, distance: fn(&str, &str) -> usize) -> f64 {
     let s1 = s1.to_lowercase();
     let s2 = s2.to_lowercase();
 
     if s1.is_empty() || s2.is_empty() {
-        return if s1.is_empty() == s2.is_empty() {
-            1.0
-        } else {
-            0.0
-        };
+        return empty_similarity(&s1, &s2);
     }
 
     if s1.contains(&s2) || s2.contains(&s1) {
@@ -128,12 +294,42 @@ pub fn match_string(s1: &str, s2: &str) -> f64 {
         return 0.0;
     }
 
-    let distance = levenshtein(&s1, &s2) as f64;
+    let distance = distance(&s1, &s2) as f64;
     let score = 1.0 - (distance / shorter_len as f64);
 
     score.clamp(0.0, 1.0)
 }
 
+/// Returns the Damerau-Levenshtein distance (optimal string alignment) between two strings.
+fn damerau_levenshtein(s1: &str, s2: &str) -> usize {
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+
+    // rows i - 2, i - 1 and i of the distance matrix
+    let mut prev_prev = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(prev_prev[j - 2] + 1);
+            }
+        }
+
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
 /// Returns a similarity score between two strings using a fuzzy matching algorithm that relies on Jaro-Winkler instead Levenshtein. Use this over [`match_string`].
 ///
 /// ## Examples
@@ -155,7 +351,8 @@ pub fn match_string(s1: &str, s2: &str) -> f64 {
 ///
 /// ## Returns
 ///
-/// The similarity score between the two strings, the score is a [f64] between 0.0 and 1.0.
+/// The similarity score between the two strings, the score is a [f64] between 0.0 and 1.0. Whitespace around the
+/// strings is ignored, after that it's `1.0` if both are empty and `0.0` if only one of them is.
 #[must_use]
 pub fn string_similarity<S1, S2>(s1: S1, s2: S2) -> f64
 where
@@ -165,13 +362,48 @@ where
     string_similarity_impl(s1.as_ref(), s2.as_ref())
 }
 
+/// Returns a similarity score between two strings like [`string_similarity`] but ignoring accents, so `"café"` and
+/// `"cafe"` are an exact match.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use handy::pattern::string_similarity_normalized;
+///
+/// let score = string_similarity_normalized("Café", "cafe");
+/// println!("Score: {}", score);
+/// ```
+///
+/// ## Arguments
+///
+/// * `s1` - The first string.
+/// * `s2` - The second string.
+///
+/// ## Returns
+///
+/// The similarity score between the two strings after decomposing them (NFD) and removing the combining marks, the
+/// score is a [f64] between 0.0 and 1.0.
+#[must_use]
+pub fn string_similarity_normalized<S1, S2>(s1: S1, s2: S2) -> f64
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    string_similarity_impl(&strip_accents(s1.as_ref()), &strip_accents(s2.as_ref()))
+}
+
+/// Decomposes a string and removes the combining marks, which leaves the base letters of the accented ones.
+fn strip_accents(s: &str) -> String {
+    s.nfd().filter(|&c| !is_combining_mark(c)).collect()
+}
+
 /// Returns a similarity score between two strings using a fuzzy matching algorithm that relies on Jaro-Winkler instead of Levenshtein.
 fn string_similarity_impl(s1: &str, s2: &str) -> f64 {
     let s1 = s1.trim().to_lowercase();
     let s2 = s2.trim().to_lowercase();
 
     if s1.is_empty() || s2.is_empty() {
-        return 0.0;
+        return empty_similarity(&s1, &s2);
     }
 
     if s1.contains(&s2) || s2.contains(&s1) {
@@ -181,6 +413,179 @@ fn string_similarity_impl(s1: &str, s2: &str) -> f64 {
     jaro_winkler(&s1, &s2)
 }
 
+/// Returns the candidates that start with the query (case-insensitive), sorted by length so the ones that add the
+/// least on top of the query come first. Meant for shell-style completion, use [`string_similarity`] for fuzzy matching.
+///
+/// ## Examples
+///
+/// ```rust
+/// use handy::pattern::prefix_matches;
+///
+/// let candidates = ["checkout", "cherry-pick", "check", "commit"];
+/// assert_eq!(prefix_matches("CHE", &candidates), vec!["check", "checkout", "cherry-pick"]);
+/// ```
+///
+/// ## Arguments
+///
+/// * `query` - The prefix to look for.
+/// * `candidates` - The strings to match against.
+///
+/// ## Returns
+///
+/// The matching candidates sorted by length ascending, candidates with the same length keep their original order.
+#[must_use]
+pub fn prefix_matches<'a>(query: &str, candidates: &'a [&str]) -> Vec<&'a str> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<&str> = candidates
+        .iter()
+        .filter(|c| c.to_lowercase().starts_with(&query))
+        .copied()
+        .collect();
+
+    matches.sort_by_key(|c| c.chars().count());
+    matches
+}
+
+/// Returns the candidate most similar to the query according to [`string_similarity`], ties go to the first candidate.
+///
+/// ## Examples
+///
+/// ```rust
+/// use handy::pattern::best_match;
+///
+/// let candidates = ["raiju", "Salvage Yard", "kissing"];
+/// assert_eq!(best_match("yard", &candidates), Some(("Salvage Yard", 1.0)));
+/// ```
+///
+/// ## Arguments
+///
+/// * `query` - The string to look for.
+/// * `candidates` - The strings to match against.
+///
+/// ## Returns
+///
+/// The best candidate and its score, or `None` if there are no candidates.
+#[must_use]
+pub fn best_match<'a>(query: &str, candidates: &'a [&str]) -> Option<(&'a str, f64)> {
+    candidates
+        .iter()
+        .map(|c| (*c, string_similarity(c, query)))
+        .fold(None, |best, (candidate, score)| match best {
+            Some((_, best_score)) if best_score >= score => best,
+            _ => Some((candidate, score)),
+        })
+}
+
+/// Returns the candidate most similar to the query like [`best_match`] but scores the candidates in parallel, meant for
+/// large candidate lists.
+///
+/// Ties still go to the first candidate: the candidates are compared by their position when the scores are equal, so
+/// the result doesn't depend on how the work was split between threads.
+///
+/// ## Examples
+///
+/// ```rust
+/// use handy::pattern::best_match_par;
+///
+/// let candidates = ["raiju", "Salvage Yard", "kissing"];
+/// assert_eq!(best_match_par("yard", &candidates), Some(("Salvage Yard", 1.0)));
+/// ```
+///
+/// ## Arguments
+///
+/// * `query` - The string to look for.
+/// * `candidates` - The strings to match against.
+///
+/// ## Returns
+///
+/// The best candidate and its score, or `None` if there are no candidates.
+#[must_use]
+pub fn best_match_par<'a>(query: &str, candidates: &'a [&str]) -> Option<(&'a str, f64)> {
+    candidates
+        .par_iter()
+        .enumerate()
+        .map(|(i, c)| (i, *c, string_similarity(c, query)))
+        .reduce_with(|a, b| {
+            if b.2 > a.2 || (b.2.total_cmp(&a.2).is_eq() && b.0 < a.0) {
+                b
+            } else {
+                a
+            }
+        })
+        .map(|(_, candidate, score)| (candidate, score))
+}
+
+/// Returns the `n` candidates most similar to the query according to [`string_similarity`], sorted by score
+/// descending. Candidates with the same score keep their original order.
+///
+/// ## Examples
+///
+/// ```rust
+/// use handy::pattern::top_matches;
+///
+/// let candidates = ["raiju", "Salvage Yard", "kissing"];
+/// assert_eq!(top_matches("yard", &candidates, 1), vec![("Salvage Yard", 1.0)]);
+/// ```
+///
+/// ## Arguments
+///
+/// * `query` - The string to look for.
+/// * `candidates` - The strings to match against.
+/// * `n` - The maximum number of matches to return.
+///
+/// ## Returns
+///
+/// Up to `n` candidates and their scores.
+#[must_use]
+pub fn top_matches<'a>(query: &str, candidates: &'a [&str], n: usize) -> Vec<(&'a str, f64)> {
+    let mut matches: Vec<(&str, f64)> = candidates
+        .iter()
+        .map(|c| (*c, string_similarity(c, query)))
+        .collect();
+
+    matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+    matches.truncate(n);
+    matches
+}
+
+/// Returns the byte indices of the characters in `target` that match `query` as a subsequence (case-insensitive),
+/// picking the first possible character each time. Meant for highlighting fuzzy matches, it doesn't affect the scores
+/// of [`string_similarity`].
+///
+/// ## Examples
+///
+/// ```rust
+/// use handy::pattern::match_indices;
+///
+/// assert_eq!(match_indices("abc", "axbxc"), vec![0, 2, 4]);
+/// assert!(match_indices("abc", "cba").is_empty());
+/// ```
+///
+/// ## Arguments
+///
+/// * `query` - The characters to look for, in order.
+/// * `target` - The string to look in.
+///
+/// ## Returns
+///
+/// The byte index in `target` of each character of `query`, or an empty vector if `target` doesn't contain all of
+/// them in order.
+#[must_use]
+pub fn match_indices(query: &str, target: &str) -> Vec<usize> {
+    let mut indices = Vec::with_capacity(query.len());
+    let mut target_chars = target.char_indices();
+
+    for q in query.chars() {
+        let found = target_chars.find(|(_, t)| t.to_lowercase().eq(q.to_lowercase()));
+        match found {
+            Some((i, _)) => indices.push(i),
+            None => return Vec::new(),
+        }
+    }
+
+    indices
+}
+
 /// Asserts that two strings have a similarity score close to the expected value.
 #[macro_export]
 macro_rules! assert_match_string {
@@ -211,19 +616,42 @@ macro_rules! assert_string_similarity {
 
 #[cfg(test)]
 mod tests {
-    use super::{glob_to_regex_pattern, match_filename_with_glob_pattern};
-    use crate::{assert_match_string, pattern::is_close_to_upper_bound};
+    use super::{
+        glob_to_regex_pattern, match_filename_with_glob_pattern, match_path_with_glob_pattern,
+        prefix_matches, GlobMatcher,
+    };
+    use crate::pattern::{
+        best_match, best_match_par, damerau_levenshtein, is_close_to_upper_bound, match_indices,
+        match_string, match_string_damerau, string_similarity, string_similarity_normalized,
+        top_matches, ERROR_MARGIN,
+    };
     use std::path::Path;
 
     #[test]
     fn test_glob_to_regex() {
-        assert_eq!(glob_to_regex_pattern("fish*.txt"), "fish.*\\.txt");
-        assert_eq!(glob_to_regex_pattern("fish?txt"), "fish.txt");
+        assert_eq!(glob_to_regex_pattern("fish*.txt"), "fish[^/]*\\.txt");
+        assert_eq!(glob_to_regex_pattern("src/**"), "src/.*");
+        assert_eq!(
+            glob_to_regex_pattern("src/**/*.rs"),
+            "src/(?:.*/)?[^/]*\\.rs"
+        );
+        assert_eq!(glob_to_regex_pattern("fish?txt"), "fish[^/]txt");
         assert_eq!(glob_to_regex_pattern("fish+txt"), "fish\\+txt");
         assert_eq!(glob_to_regex_pattern("fish\\txt"), "fish\\\\txt");
         assert_eq!(glob_to_regex_pattern("fish\\(txt"), "fish\\\\\\(txt");
     }
 
+    #[test]
+    fn test_glob_to_regex_classes() {
+        assert_eq!(glob_to_regex_pattern("file[abc].txt"), "file[abc]\\.txt");
+        assert_eq!(glob_to_regex_pattern("file[0-9].txt"), "file[0-9]\\.txt");
+        assert_eq!(glob_to_regex_pattern("file[!0-9].txt"), "file[^0-9]\\.txt");
+        assert_eq!(glob_to_regex_pattern("file[^a].txt"), "file[^a]\\.txt");
+        assert_eq!(glob_to_regex_pattern("file[]a]"), "file[\\]a]");
+        assert_eq!(glob_to_regex_pattern("file[.txt"), "file\\[\\.txt");
+        assert_eq!(glob_to_regex_pattern("file]"), "file\\]");
+    }
+
     #[test]
     fn test_is_close_to_upper_bound() {
         assert!(is_close_to_upper_bound(1.0));
@@ -246,6 +674,135 @@ mod tests {
             Path::new("fish.txt"),
             "f*.jpg"
         ));
+
+        assert!(match_filename_with_glob_pattern(
+            Path::new("file3.txt"),
+            "file[0-9].txt"
+        ));
+        assert!(!match_filename_with_glob_pattern(
+            Path::new("filex.txt"),
+            "file[0-9].txt"
+        ));
+        assert!(match_filename_with_glob_pattern(
+            Path::new("fileb.txt"),
+            "file[abc].txt"
+        ));
+        assert!(match_filename_with_glob_pattern(
+            Path::new("filex.txt"),
+            "file[!0-9].txt"
+        ));
+        assert!(!match_filename_with_glob_pattern(
+            Path::new("file3.txt"),
+            "file[!0-9].txt"
+        ));
+        assert!(match_filename_with_glob_pattern(
+            Path::new("file[.txt"),
+            "file[.txt"
+        ));
+    }
+
+    #[test]
+    fn test_glob_matcher() {
+        let matcher = GlobMatcher::new("file[0-9].txt").expect("Failed to compile glob");
+        assert_eq!(matcher.pattern(), "file[0-9].txt");
+
+        for i in 0..10 {
+            let name = format!("file{i}.txt");
+            assert!(matcher.is_match_str(&name));
+            assert!(matcher.is_match(&Path::new("dir").join(&name)));
+            assert_eq!(
+                matcher.is_match(Path::new(&name)),
+                match_filename_with_glob_pattern(Path::new(&name), "file[0-9].txt")
+            );
+        }
+        for name in ["filex.txt", "file.txt", "file1.rs"] {
+            assert!(!matcher.is_match_str(name));
+            assert!(!matcher.is_match(Path::new(name)));
+        }
+        assert!(!matcher.is_match(Path::new("/")));
+
+        // the whole filename has to match
+        let matcher = GlobMatcher::new("*.txt").expect("Failed to compile glob");
+        assert!(matcher.is_match_str("a.txt"));
+        assert!(!matcher.is_match_str("a.txt.bak"));
+        assert!(!matcher.is_match(Path::new("dir/a.txt.bak")));
+
+        assert!(GlobMatcher::new("file[z-a].txt").is_err());
+    }
+
+    #[test]
+    fn test_match_path_with_glob_pattern() {
+        assert!(match_path_with_glob_pattern(
+            Path::new("src/a/b/c.rs"),
+            "src/**/*.rs"
+        ));
+        assert!(match_path_with_glob_pattern(
+            Path::new("src/c.rs"),
+            "src/**/*.rs"
+        ));
+        assert!(match_path_with_glob_pattern(
+            Path::new("src/a/b/c.rs"),
+            "src/**"
+        ));
+        assert!(match_path_with_glob_pattern(
+            Path::new("src/c.rs"),
+            "src/*.rs"
+        ));
+        assert!(!match_path_with_glob_pattern(
+            Path::new("src/a/b/c.rs"),
+            "src/*.rs"
+        ));
+        assert!(!match_path_with_glob_pattern(
+            Path::new("src/a/b/c.txt"),
+            "src/**/*.rs"
+        ));
+        assert!(!match_path_with_glob_pattern(
+            Path::new("other/src/c.rs"),
+            "src/**/*.rs"
+        ));
+
+        // `?` doesn't cross directories, like `*`
+        assert!(match_path_with_glob_pattern(Path::new("axb"), "a?b"));
+        assert!(!match_path_with_glob_pattern(Path::new("a/b"), "a?b"));
+        assert!(!match_path_with_glob_pattern(Path::new("a/b"), "a*b"));
+    }
+
+    #[test]
+    fn test_invalid_glob_pattern_matches_nothing() {
+        for path in ["fish.txt", "file[z-a].txt", "dir/fish.txt"] {
+            assert!(!match_filename_with_glob_pattern(
+                Path::new(path),
+                "file[z-a].txt"
+            ));
+            assert!(!match_path_with_glob_pattern(
+                Path::new(path),
+                "file[z-a].txt"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_prefix_matches() {
+        let candidates = [
+            "checkout",
+            "cherry-pick",
+            "check",
+            "commit",
+            "Changelog",
+            "ch",
+        ];
+
+        assert_eq!(
+            prefix_matches("che", &candidates),
+            vec!["check", "checkout", "cherry-pick"]
+        );
+        assert_eq!(
+            prefix_matches("CH", &candidates),
+            vec!["ch", "check", "checkout", "Changelog", "cherry-pick"]
+        );
+        assert_eq!(prefix_matches("commit", &candidates), vec!["commit"]);
+        assert!(prefix_matches("push", &candidates).is_empty());
+        assert_eq!(prefix_matches("", &candidates).len(), candidates.len());
     }
 
     #[test]
@@ -255,6 +812,109 @@ mod tests {
         assert_match_string!("raiju", "yard", 0.0);
     }
 
+    #[test]
+    fn test_match_string_damerau() {
+        assert_match_string!("teh", "the", 0.333);
+        assert!((match_string_damerau("teh", "the") - 0.667).abs() < ERROR_MARGIN);
+        assert!(match_string_damerau("teh", "the") > match_string("teh", "the"));
+        assert!(match_string_damerau("recieve", "receive") > match_string("recieve", "receive"));
+
+        // same as match_string without transpositions
+        assert!((match_string_damerau("kitten", "kissing") - 0.333).abs() < ERROR_MARGIN);
+        assert!(is_close_to_upper_bound(match_string_damerau(
+            "Salvage Yard",
+            "yard"
+        )));
+        assert!(match_string_damerau("raiju", "yard").abs() < ERROR_MARGIN);
+
+        assert_eq!(damerau_levenshtein("", "abc"), 3);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 3);
+        assert_eq!(damerau_levenshtein("abcd", "acbd"), 1);
+    }
+
+    #[test]
+    fn test_empty_strings() {
+        let score_fns: [fn(&str, &str) -> f64; 3] =
+            [match_string, match_string_damerau, |s1, s2| {
+                string_similarity(s1, s2)
+            }];
+        for score_fn in score_fns {
+            assert!(is_close_to_upper_bound(score_fn("", "")));
+            assert!(score_fn("", "hello").abs() < ERROR_MARGIN);
+            assert!(score_fn("hello", "").abs() < ERROR_MARGIN);
+        }
+
+        // only string_similarity ignores whitespace
+        assert!(is_close_to_upper_bound(string_similarity("  ", "")));
+        assert!(string_similarity("hello", " ").abs() < ERROR_MARGIN);
+        assert!(match_string("hello", " ").abs() < ERROR_MARGIN);
+    }
+
+    #[test]
+    fn test_best_match() {
+        let candidates = ["raiju", "kissing", "Salvage Yard"];
+
+        let (winner, score) = best_match("kitten", &candidates).expect("No best match");
+        assert_eq!(winner, "kissing");
+        assert!((score - 0.714).abs() < ERROR_MARGIN, "{score}");
+
+        let (winner, score) = best_match("yard", &candidates).expect("No best match");
+        assert_eq!(winner, "Salvage Yard");
+        assert!(is_close_to_upper_bound(score));
+
+        // ties go to the first candidate
+        assert_eq!(best_match("ab", &["abc", "abd"]), Some(("abc", 1.0)));
+        assert_eq!(best_match("yard", &[]), None);
+    }
+
+    #[test]
+    fn test_best_match_par() {
+        let names: Vec<String> = (0..100_000)
+            .map(|i| format!("candidate-{}-{}", i % 97, i * 7919 % 10_007))
+            .collect();
+        let candidates: Vec<&str> = names.iter().map(String::as_str).collect();
+
+        for query in ["candidate-42-1234", "cand", "xyz", "didate-9"] {
+            assert_eq!(
+                best_match_par(query, &candidates),
+                best_match(query, &candidates),
+                "{query}"
+            );
+        }
+
+        assert_eq!(best_match_par("ab", &["abc", "abd"]), Some(("abc", 1.0)));
+        assert_eq!(best_match_par("yard", &[]), None);
+    }
+
+    #[test]
+    fn test_top_matches() {
+        let candidates = ["raiju", "kissing", "Salvage Yard", "yard"];
+
+        let top = top_matches("yard", &candidates, 2);
+        assert_eq!(top, vec![("Salvage Yard", 1.0), ("yard", 1.0)]);
+
+        let all = top_matches("kitten", &candidates, 10);
+        assert_eq!(all.len(), candidates.len());
+        assert_eq!(all[0].0, "kissing");
+        assert!((all[0].1 - 0.714).abs() < ERROR_MARGIN, "{}", all[0].1);
+        assert!(all.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        assert!(top_matches("yard", &candidates, 0).is_empty());
+        assert!(top_matches("yard", &[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_match_indices() {
+        assert_eq!(match_indices("abc", "axbxc"), vec![0, 2, 4]);
+        assert_eq!(match_indices("ABC", "aXbXc"), vec![0, 2, 4]);
+        assert_eq!(match_indices("aa", "banana"), vec![1, 3]);
+        assert_eq!(match_indices("yd", "Salvage Yard"), vec![8, 11]);
+        assert_eq!(match_indices("éa", "xéa"), vec![1, 3]);
+        assert!(match_indices("abc", "cba").is_empty());
+        assert!(match_indices("abc", "ab").is_empty());
+        assert!(match_indices("", "abc").is_empty());
+    }
+
     #[test]
     fn test_string_similarity() {
         assert_string_similarity!("kitten", "kissing", 0.714);
@@ -262,4 +922,21 @@ mod tests {
         assert_string_similarity!("Salvage Yard", "yad", 0.472);
         assert_string_similarity!("raiju", "yard", 0.483);
     }
+
+    #[test]
+    fn test_string_similarity_normalized() {
+        assert!(string_similarity("café", "cafe") < 1.0);
+        assert!((string_similarity_normalized("café", "cafe") - 1.0).abs() < ERROR_MARGIN);
+        assert!((string_similarity_normalized("naïve", "naive") - 1.0).abs() < ERROR_MARGIN);
+        assert!(
+            (string_similarity_normalized("Crème Brûlée", "creme brulee") - 1.0).abs()
+                < ERROR_MARGIN
+        );
+        assert!(
+            (string_similarity_normalized("kitten", "kissing")
+                - string_similarity("kitten", "kissing"))
+            .abs()
+                < ERROR_MARGIN
+        );
+    }
 }
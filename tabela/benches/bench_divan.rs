@@ -0,0 +1,5 @@
+mod table;
+
+fn main() {
+    divan::main();
+}
@@ -0,0 +1,59 @@
+use divan::{black_box, Bencher};
+use tabela::{Cell, Color, Row, Table};
+
+struct Person {
+    name: String,
+    age: u8,
+    score: f64,
+}
+
+struct PlainPerson<'a>(&'a Person);
+
+impl Row for &Person {
+    fn as_row(&self) -> Vec<Cell> {
+        vec![
+            Cell::new(&self.name).with_color(Color::Green),
+            Cell::new(self.age).with_color(Color::Cyan),
+            Cell::new(self.score).with_color(Color::Yellow),
+        ]
+    }
+}
+
+impl Row for &PlainPerson<'_> {
+    fn as_row(&self) -> Vec<Cell> {
+        vec![
+            Cell::new(&self.0.name),
+            Cell::new(self.0.age),
+            Cell::new(self.0.score),
+        ]
+    }
+}
+
+fn people(n: usize) -> Vec<Person> {
+    (0..n)
+        .map(|i| Person {
+            name: format!("Person {i}"),
+            age: u8::try_from(i % 100).unwrap(),
+            score: f64::from(u32::try_from(i).unwrap()) / 7.0,
+        })
+        .collect()
+}
+
+#[divan::bench(name = "format_plain", args = [100, 1_000, 10_000])]
+fn bench_format_plain(bencher: Bencher, n: usize) {
+    let data = people(n);
+    let plain: Vec<PlainPerson> = data.iter().map(PlainPerson).collect();
+    let refs: Vec<&PlainPerson> = plain.iter().collect();
+    let table = Table::new(&refs).with_header(&["Name", "Age", "Score"], None, None, None);
+
+    bencher.bench_local(|| black_box(table.format().unwrap()));
+}
+
+#[divan::bench(name = "format_colored", args = [100, 1_000, 10_000])]
+fn bench_format_colored(bencher: Bencher, n: usize) {
+    let data = people(n);
+    let refs: Vec<&Person> = data.iter().collect();
+    let table = Table::new(&refs).with_header(&["Name", "Age", "Score"], None, None, None);
+
+    bencher.bench_local(|| black_box(table.format().unwrap()));
+}
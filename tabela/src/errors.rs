@@ -12,4 +12,12 @@ pub enum TableError {
     /// Row length does not match first row length
     #[error("row length ({0}) must match first row length ({1})")]
     RowLengthMismatch(usize, usize),
+
+    /// The table has no header
+    #[error("table must have a header")]
+    MissingHeader,
+
+    /// Column index from the column order is out of range
+    #[error("column index ({0}) is out of range for a row of length {1}")]
+    ColumnIndexOutOfRange(usize, usize),
 }
@@ -6,12 +6,12 @@
 //!
 //! ## Concepts
 //!
-//! * **Table**: A table is a collection of rows, it also stores the header (if provided) and the separator of the cells. **Note that for performance reasons the table stores the rows as `&[&R]` instead of `Vec<R>`**.
+//! * **Table**: A table is a collection of rows, it also stores the header (if provided) and the separator of the cells. **Note that for performance reasons the table stores the rows as `&[&R]` instead of `Vec<R>`**, a table can also be collected from an iterator of owned rows at the cost of keeping their cells in memory.
 //! * **Row**: A row is a trait that represents a row of data in a table, it must implement the `as_row` method that returns a vector of cells. For example if you have a data of type `Vec<Person>` you'd have to implement the `Row` trait for `&Person`, refer to the example below.
 //! * **Cell**: A cell is a struct that represents a cell in a table, it stores the string value of a type `V` that implements the `Display` trait, as well as the color (optional), style (optional) and alignment (left by default).
 //! * **Color**: Re-export of [`colored::Color`](https://docs.rs/colored/latest/colored/enum.Color.html).
 //! * **CellStyle**: A enum that represents the style of a cell, it can be `Bold`, `Italic` or `Dimmed`.
-//! * **Alignment**: A enum that represents the alignment of a cell, it can be `Left`, `Center` or `Right`.
+//! * **Alignment**: A enum that represents the alignment of a cell, it can be `Left`, `Center`, `CenterRight` or `Right`.
 //! * **TableError**: A enum that represents the errors that can occur when formatting a table.
 //!
 //! ## Installation
@@ -28,7 +28,7 @@
 //! ## Usage
 //!
 //! ```rust
-//! use tabela::{Alignment, Cell, Color, Row, Table};
+//! use tabela::{Alignment, Cell, CellStyle, Color, Row, Table};
 //!
 //! // row type
 //! struct Person {
@@ -94,6 +94,7 @@ pub use colored::Color;
 use colored::{ColoredString, Colorize};
 pub use errors::{Result, TableError};
 use std::fmt::{Display, Write as _};
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 /// A trait that represents a row of data in a [Table]
@@ -129,12 +130,13 @@ pub trait Row {
 }
 
 /// A struct that represents a cell in a [Table]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Cell {
     pub value: String,
     pub color: Option<Color>,
     pub style: Option<CellStyle>,
     pub alignment: Alignment,
+    pub span_all: bool,
 }
 
 impl Cell {
@@ -166,6 +168,37 @@ impl Cell {
             color: None,
             style: None,
             alignment: Alignment::Left,
+            span_all: false,
+        }
+    }
+
+    /// Creates a new [Cell] that spans all the columns of the [Table], a row made of only this cell is written across the
+    /// whole width of the table and is not taken into account when calculating the column widths, which is useful for
+    /// section headers like `── Results ──`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `value` - The value to add to the cell
+    ///
+    /// ## Returns
+    ///
+    /// A new [Cell] that spans all the columns
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Alignment, Cell};
+    ///
+    /// let cell = Cell::spanning("── Results ──").with_alignment(Alignment::Center);
+    /// ```
+    #[must_use]
+    pub fn spanning<V>(value: V) -> Self
+    where
+        V: Display,
+    {
+        Cell {
+            span_all: true,
+            ..Cell::new(value)
         }
     }
 
@@ -239,6 +272,16 @@ impl Cell {
         self.style = Some(style);
         self
     }
+
+    /// Whether the [Cell] has no color or style, in which case it displays as its value
+    fn is_plain(&self) -> bool {
+        self.color.is_none() && self.style.is_none()
+    }
+}
+
+/// Whether a row is made of a single [spanning](Cell::spanning) cell
+fn is_spanning(cells: &[Cell]) -> bool {
+    matches!(cells, [cell] if cell.span_all)
 }
 
 impl Display for Cell {
@@ -268,6 +311,7 @@ impl From<String> for Cell {
             color: None,
             style: None,
             alignment: Alignment::Left,
+            span_all: false,
         }
     }
 }
@@ -279,6 +323,7 @@ impl From<&str> for Cell {
             color: None,
             style: None,
             alignment: Alignment::Left,
+            span_all: false,
         }
     }
 }
@@ -302,9 +347,12 @@ pub enum Alignment {
     /// Aligns the cell to the left
     Left,
 
-    /// Aligns the cell to the center
+    /// Aligns the cell to the center, if the padding is odd the extra space goes to the right
     Center,
 
+    /// Aligns the cell to the center, if the padding is odd the extra space goes to the left
+    CenterRight,
+
     /// Aligns the cell to the right
     Right,
 }
@@ -318,6 +366,14 @@ where
     pub header: Vec<Cell>,
     pub rows: &'a [&'a R],
     pub separator: String,
+    pub panel_title: Option<String>,
+    pub caption: Option<String>,
+    pub column_order: Option<Vec<usize>>,
+    pub trim_trailing: bool,
+    pub column_alignments: Vec<Alignment>,
+    pub min_widths: Vec<usize>,
+    pub repeat_header_every: usize,
+    collected_rows: Vec<Vec<Cell>>,
 }
 
 impl<'a, R> Table<'a, R> {
@@ -369,6 +425,14 @@ impl<'a, R> Table<'a, R> {
             header: Vec::new(),
             rows,
             separator: String::from(" "),
+            panel_title: None,
+            caption: None,
+            column_order: None,
+            trim_trailing: false,
+            column_alignments: Vec::new(),
+            min_widths: Vec::new(),
+            repeat_header_every: 0,
+            collected_rows: Vec::new(),
         }
     }
 
@@ -448,6 +512,49 @@ impl<'a, R> Table<'a, R> {
         self
     }
 
+    /// Adds a header to the [Table] from already built cells, so each column can have its own color, style and
+    /// alignment. Like [`Table::with_header`] the length is checked against the rows when the table is formatted.
+    ///
+    /// ## Arguments
+    ///
+    /// * `cells` - The header cells
+    ///
+    /// ## Returns
+    ///
+    /// A new [Table] with the given header
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Alignment, Cell, Color, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person {
+    ///     name: "Johnny".into(),
+    ///     age: 30,
+    /// }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_header_cells(vec![
+    ///     Cell::new("Name").with_color(Color::Red),
+    ///     Cell::new("Age").with_color(Color::Green).with_alignment(Alignment::Right),
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn with_header_cells(mut self, cells: Vec<Cell>) -> Self {
+        self.header = cells;
+        self
+    }
+
     /// Sets the separator for the table cells.
     ///
     /// Default: `" "`
@@ -500,27 +607,59 @@ impl<'a, R> Table<'a, R> {
         self.separator = separator.as_ref().to_string();
         self
     }
-}
 
-impl<'a, R> Table<'a, R>
-where
-    &'a R: Row,
-{
-    /// Formats the table into a string
+    /// Sets a caption for the [Table], it's written on its own line after the rows, or embedded in the bottom border if the table is rendered [as a panel](Table::as_panel).
+    ///
+    /// ## Arguments
+    ///
+    /// * `caption` - The caption of the table
     ///
     /// ## Returns
     ///
-    /// A string representation of the table
+    /// A new [Table] with the given caption
     ///
-    /// ## Errors
+    /// ## Example
     ///
-    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
-    /// - [`TableError::RowLengthMismatch`]: Row length does not match first row length
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into() }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_caption("1 person");
+    /// ```
+    #[must_use]
+    pub fn with_caption<S>(mut self, caption: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.caption = Some(caption.as_ref().to_string());
+        self
+    }
+
+    /// Renders the [Table] enclosed in a box with the given title embedded in the top border, if a [caption](Table::with_caption) is set it's embedded in the bottom border.
+    ///
+    /// ## Arguments
+    ///
+    /// * `title` - The title of the panel
+    ///
+    /// ## Returns
+    ///
+    /// A new [Table] that is rendered as a panel
     ///
     /// ## Example
     ///
     /// ```rust,no_run
-    /// use tabela::{Cell, CellStyle, Color, Row, Table};
+    /// use tabela::{Cell, Row, Table};
     ///
     /// struct Person {
     ///     name: String,
@@ -529,179 +668,1675 @@ where
     ///
     /// impl Row for &Person {
     ///     fn as_row(&self) -> Vec<Cell> {
-    ///         vec![
-    ///             Cell::new(&self.name).with_color(Color::Green),
-    ///             Cell::new(self.age).with_color(Color::Cyan),
-    ///         ]
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
     ///     }
     /// }
     ///
-    /// let data = [
-    ///     Person {
-    ///         name: "Johnny".into(),
-    ///         age: 30,
-    ///     },
-    ///     Person {
-    ///         name: "Jane".into(),
-    ///         age: 25,
-    ///     },
-    /// ];
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
     /// let data_refs: Vec<&Person> = data.iter().collect();
-    /// let table: Table<'_, Person> = Table::new(&data_refs).with_header(&["Name", "Age"], None, Some(CellStyle::Bold), None);
-    /// let formatted = table.format().unwrap();
-    ///
-    /// println!("{formatted}");
+    /// let table: Table<'_, Person> = Table::new(&data_refs).as_panel("People");
+    /// println!("{}", table.format().unwrap());
+    ///
+    /// // Output:
+    /// //
+    /// // ┌─ People ──┐
+    /// // │ Johnny 30 │
+    /// // └───────────┘
     /// ```
-    pub fn format(&self) -> Result<String> {
-        let mut col_widths: Vec<usize> = Vec::new();
-        if !self.header.is_empty() {
-            col_widths = self
-                .header
-                .iter()
-                .map(|c| UnicodeWidthStr::width(c.value.as_str()))
-                .collect();
-        }
-
-        if !self.rows.is_empty() {
-            let first_row_len = self.rows[0].as_row().len();
-
-            if !self.header.is_empty() && self.header.len() != first_row_len {
-                return Err(TableError::HeaderLengthMismatch(
-                    self.header.len(),
-                    first_row_len,
-                ));
-            }
-
-            if self.header.is_empty() && first_row_len > 0 {
-                col_widths = vec![0; first_row_len];
-            }
-
-            for row in self.rows {
-                let row_values = row.as_row();
-
-                if row_values.len() != col_widths.len() && !self.header.is_empty() {
-                    return Err(TableError::HeaderLengthMismatch(
-                        row_values.len(),
-                        col_widths.len(),
-                    ));
-                }
-                if row_values.len() != first_row_len {
-                    return Err(TableError::RowLengthMismatch(
-                        row_values.len(),
-                        first_row_len,
-                    ));
-                }
-
-                for (i, value) in row_values.iter().enumerate() {
-                    let cell_content_width = UnicodeWidthStr::width(value.value.as_str());
-                    if i < col_widths.len() {
-                        col_widths[i] = col_widths[i].max(cell_content_width);
-                    } else {
-                        col_widths.push(cell_content_width);
-                    }
-                }
-            }
-        }
-
-        let mut output = String::new();
-        if !self.header.is_empty() {
-            for (i, header_cell) in self.header.iter().enumerate() {
-                if i < col_widths.len() {
-                    let header_display = format!("{header_cell}");
-                    let header_content_width = UnicodeWidthStr::width(header_cell.value.as_str());
-                    let required_width = col_widths[i];
-                    let padding = required_width.saturating_sub(header_content_width);
-
-                    format_cell(&mut output, header_cell.alignment, &header_display, padding);
-
-                    if i < self.header.len() - 1 {
-                        write!(output, "{}", self.separator).unwrap();
-                    }
-                } else {
-                    write!(output, "{header_cell}").unwrap();
-
-                    if i < self.header.len() - 1 {
-                        write!(output, "{}", self.separator).unwrap();
-                    }
-                }
-            }
-
-            writeln!(output).unwrap();
-        }
-
-        for row in self.rows {
-            let row_values = row.as_row();
-            for (i, value_cell) in row_values.iter().enumerate() {
-                if i >= col_widths.len() {
-                    write!(output, "{value_cell}").unwrap();
-                } else {
-                    let value_display = format!("{value_cell}");
-                    let value_content_width = UnicodeWidthStr::width(value_cell.value.as_str());
-                    let required_width = col_widths[i];
-                    let padding = required_width.saturating_sub(value_content_width);
-
-                    format_cell(&mut output, value_cell.alignment, &value_display, padding);
-                }
-
-                if i < row_values.len() - 1 {
-                    write!(output, "{}", self.separator).unwrap();
-                }
-            }
-
-            writeln!(output).unwrap();
-        }
-
-        Ok(output)
+    #[must_use]
+    pub fn as_panel(mut self, title: &str) -> Self {
+        self.panel_title = Some(title.to_string());
+        self
     }
-}
-
-/// Formats a [Cell] to a string.
-///
-/// ## Arguments
-///
-/// * `output` - The string to write to.
-/// * `alignment` - The alignment of the cell.
-/// * `value` - The value of the cell.
-/// * `padding` - The padding to add to the cell.
-fn format_cell(output: &mut String, alignment: Alignment, value: &str, padding: usize) {
-    match alignment {
-        Alignment::Left => {
-            write!(output, "{value}{}", " ".repeat(padding)).unwrap();
-        }
-        Alignment::Center => {
-            let left_padding = padding / 2;
-            let right_padding = padding - left_padding;
 
-            write!(
-                output,
-                "{}{value}{}",
-                " ".repeat(left_padding),
-                " ".repeat(right_padding)
-            )
-            .unwrap();
-        }
-        Alignment::Right => {
-            write!(output, "{}{value}", " ".repeat(padding)).unwrap();
-        }
+    /// Sets the order of the columns by their index in [`Row::as_row`], columns not in `order` are omitted. The same order is applied to the header.
+    ///
+    /// ## Arguments
+    ///
+    /// * `order` - The indices of the columns to display, in display order
+    ///
+    /// ## Returns
+    ///
+    /// A new [Table] with the given column order
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs)
+    ///     .with_header(&["Name", "Age"], None, None, None)
+    ///     .with_column_order(&[1, 0]);  // age first
+    /// ```
+    #[must_use]
+    pub fn with_column_order(mut self, order: &[usize]) -> Self {
+        self.column_order = Some(order.to_vec());
+        self
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use handy::iter::IntoRefVec;
 
-    #[test]
-    fn test_table() {
-        #[derive(Debug)]
-        struct Person {
-            name: String,
-            age: u8,
-        }
+    /// Sets whether trailing spaces are removed from each line, which otherwise are added as padding to the last column
+    /// if it's left or center aligned. Lines of a table rendered [as a panel](Table::as_panel) are never trimmed since
+    /// they're padded to the panel's width anyway.
+    ///
+    /// Default: `false`
+    ///
+    /// ## Arguments
+    ///
+    /// * `trim` - Whether to remove trailing spaces
+    ///
+    /// ## Returns
+    ///
+    /// A new [Table] with the given trimming behavior
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs)
+    ///     .with_header(&["Name", "Some Age"], None, None, None)
+    ///     .trim_trailing(true);  // "Johnny 30" instead of "Johnny 30      "
+    /// ```
+    #[must_use]
+    pub fn trim_trailing(mut self, trim: bool) -> Self {
+        self.trim_trailing = trim;
+        self
+    }
+
+    /// Sets the default alignment of each column by their index in [`Row::as_row`], it's used for the cells (including the header) that still have the default [`Alignment::Left`], so an explicit alignment on a cell takes precedence. Columns past the end of `alignments` are left as they are.
+    ///
+    /// ## Arguments
+    ///
+    /// * `alignments` - The alignment of each column
+    ///
+    /// ## Returns
+    ///
+    /// A new [Table] with the given column alignments
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Alignment, Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs)
+    ///     .with_header(&["Name", "Age"], None, None, None)
+    ///     .with_column_alignments(&[Alignment::Left, Alignment::Right]);  // age is right-aligned
+    /// ```
+    #[must_use]
+    pub fn with_column_alignments(mut self, alignments: &[Alignment]) -> Self {
+        self.column_alignments = alignments.to_vec();
+        self
+    }
+
+    /// Sets the minimum width of each column by their index in [`Row::as_row`], columns with narrower content are padded to it. Columns past the end of `widths` have no minimum.
+    ///
+    /// ## Arguments
+    ///
+    /// * `widths` - The minimum width of each column
+    ///
+    /// ## Returns
+    ///
+    /// A new [Table] with the given minimum widths
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_min_widths(&[10, 5]);
+    /// ```
+    #[must_use]
+    pub fn with_min_widths(mut self, widths: &[usize]) -> Self {
+        self.min_widths = widths.to_vec();
+        self
+    }
+
+    /// Sets how often the header is written again, after every `rows` rows, which helps to follow long tables in a
+    /// scrolling terminal. A value of `0` never repeats the header.
+    ///
+    /// Default: `0`
+    ///
+    /// ## Arguments
+    ///
+    /// * `rows` - The number of rows between each header
+    ///
+    /// ## Returns
+    ///
+    /// A new [Table] that repeats the header
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into() }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs)
+    ///     .with_header(&["Name"], None, None, None)
+    ///     .repeat_header_every(20);
+    /// ```
+    #[must_use]
+    pub fn repeat_header_every(mut self, rows: usize) -> Self {
+        self.repeat_header_every = rows;
+        self
+    }
+
+    /// Applies the [column alignments](Table::with_column_alignments) to the cells that have the default alignment
+    ///
+    /// ## Arguments
+    ///
+    /// * `cells` - The cells in their original order
+    fn align_cells(&self, mut cells: Vec<Cell>) -> Vec<Cell> {
+        for (cell, &alignment) in cells.iter_mut().zip(&self.column_alignments) {
+            if cell.alignment == Alignment::Left {
+                cell.alignment = alignment;
+            }
+        }
+
+        cells
+    }
+
+    /// Returns the header cells in the [column order](Table::with_column_order)
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::ColumnIndexOutOfRange`]: A column index is out of range for the header
+    fn header_cells(&self) -> Result<Vec<Cell>> {
+        let cells: Vec<Cell> = self.header.clone();
+        if cells.is_empty() {
+            return Ok(cells);
+        }
+
+        self.order_cells(self.align_cells(cells))
+    }
+
+    /// Applies the [column alignments](Table::with_column_alignments) and [column order](Table::with_column_order) to
+    /// the cells of a row, [spanning](Cell::spanning) rows are returned as they are
+    ///
+    /// ## Arguments
+    ///
+    /// * `cells` - The cells in their original order
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::ColumnIndexOutOfRange`]: A column index is out of range for the cells
+    fn prepare_row(&self, cells: Vec<Cell>) -> Result<Vec<Cell>> {
+        if is_spanning(&cells) {
+            return Ok(cells);
+        }
+
+        self.order_cells(self.align_cells(cells))
+    }
+
+    /// Reorders and filters the cells according to the [column order](Table::with_column_order), if set
+    ///
+    /// ## Arguments
+    ///
+    /// * `cells` - The cells in their original order
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::ColumnIndexOutOfRange`]: A column index is out of range for the cells
+    fn order_cells(&self, cells: Vec<Cell>) -> Result<Vec<Cell>> {
+        let Some(order) = &self.column_order else {
+            return Ok(cells);
+        };
+
+        order
+            .iter()
+            .map(|&i| {
+                cells
+                    .get(i)
+                    .cloned()
+                    .ok_or(TableError::ColumnIndexOutOfRange(i, cells.len()))
+            })
+            .collect()
+    }
+}
+
+impl<'a, R> Table<'a, R>
+where
+    &'a R: Row,
+{
+    /// Checks that the header and rows of the table have matching lengths without formatting it, a table without rows is always valid
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
+    /// - [`TableError::RowLengthMismatch`]: Row length does not match first row length
+    /// - [`TableError::ColumnIndexOutOfRange`]: A [column index](Table::with_column_order) is out of range
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table, TableError};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_header(&["Name"], None, None, None);
+    ///
+    /// assert_eq!(table.validate(), Err(TableError::HeaderLengthMismatch(1, 2)));
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        self.column_widths().map(|_| ())
+    }
+
+    /// Returns the width of each formatted line of the table, which is the sum of the column widths and the separators
+    /// between them. The border added by [`Table::as_panel`] is not included.
+    ///
+    /// ## Returns
+    ///
+    /// The width of the table in terminal columns
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
+    /// - [`TableError::RowLengthMismatch`]: Row length does not match first row length
+    /// - [`TableError::ColumnIndexOutOfRange`]: A [column index](Table::with_column_order) is out of range
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_header(&["Name", "Age"], None, None, None);
+    ///
+    /// println!("{}", "-".repeat(table.total_width().unwrap()));
+    /// println!("{}", table.format().unwrap());
+    /// ```
+    pub fn total_width(&self) -> Result<usize> {
+        Ok(table_width(&self.column_widths()?, &self.separator))
+    }
+
+    /// Formats the table into a string
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the table
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
+    /// - [`TableError::RowLengthMismatch`]: Row length does not match first row length
+    /// - [`TableError::ColumnIndexOutOfRange`]: A [column index](Table::with_column_order) is out of range
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, CellStyle, Color, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![
+    ///             Cell::new(&self.name).with_color(Color::Green),
+    ///             Cell::new(self.age).with_color(Color::Cyan),
+    ///         ]
+    ///     }
+    /// }
+    ///
+    /// let data = [
+    ///     Person {
+    ///         name: "Johnny".into(),
+    ///         age: 30,
+    ///     },
+    ///     Person {
+    ///         name: "Jane".into(),
+    ///         age: 25,
+    ///     },
+    /// ];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_header(&["Name", "Age"], None, Some(CellStyle::Bold), None);
+    /// let formatted = table.format().unwrap();
+    ///
+    /// println!("{formatted}");
+    /// ```
+    pub fn format(&self) -> Result<String> {
+        let col_widths = self.column_widths()?;
+
+        let mut output = String::new();
+        let header = self.header_cells()?;
+        if !header.is_empty() {
+            self.write_cells(&mut output, &header, &col_widths);
+        }
+
+        let total_width = table_width(&col_widths, &self.separator);
+        for (i, row) in self.all_row_cells().enumerate() {
+            let every = self.repeat_header_every;
+            if every > 0 && i > 0 && i % every == 0 && !header.is_empty() {
+                self.write_cells(&mut output, &header, &col_widths);
+            }
+
+            let row = row?;
+            if is_spanning(&row) {
+                self.write_cells(&mut output, &row, &[total_width]);
+            } else {
+                self.write_cells(&mut output, &row, &col_widths);
+            }
+        }
+
+        if let Some(title) = &self.panel_title {
+            return Ok(self.wrap_in_panel(&output, title, total_width));
+        }
+
+        if let Some(caption) = &self.caption {
+            writeln!(output, "{caption}").unwrap();
+        }
+
+        Ok(output)
+    }
+
+    /// Converts the rows into JSON objects keyed by the header cell values, with the cell values as strings.
+    /// [Spanning](Cell::spanning) rows are skipped since they're not records.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// ## Returns
+    ///
+    /// A vector of JSON objects, one per row
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::MissingHeader`]: The table has no header
+    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
+    /// - [`TableError::ColumnIndexOutOfRange`]: A [column index](Table::with_column_order) is out of range
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_header(&["Name", "Age"], None, None, None);
+    /// let records = table.to_records().unwrap();
+    ///
+    /// println!("{}", serde_json::to_string(&records).unwrap()); // [{"Age":"30","Name":"Johnny"}]
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_records(&self) -> Result<Vec<serde_json::Map<String, serde_json::Value>>> {
+        let header = self.header_cells()?;
+        if header.is_empty() {
+            return Err(TableError::MissingHeader);
+        }
+
+        self.all_row_cells()
+            .filter(|row_values| !row_values.as_ref().is_ok_and(|r| is_spanning(r)))
+            .map(|row_values| {
+                let row_values = row_values?;
+                if row_values.len() != header.len() {
+                    return Err(TableError::HeaderLengthMismatch(
+                        header.len(),
+                        row_values.len(),
+                    ));
+                }
+
+                Ok(header
+                    .iter()
+                    .zip(row_values)
+                    .map(|(header_cell, value_cell)| {
+                        (
+                            header_cell.value.clone(),
+                            serde_json::Value::String(value_cell.value),
+                        )
+                    })
+                    .collect())
+            })
+            .collect()
+    }
+
+    /// Converts the table into a JSON string with the header and rows as arrays of the cell values, ignoring colors and styles.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// ## Returns
+    ///
+    /// A JSON string in the form `{"header": [...], "rows": [[...], ...]}`, the header is empty if the table has none
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
+    /// - [`TableError::RowLengthMismatch`]: Row length does not match first row length
+    /// - [`TableError::ColumnIndexOutOfRange`]: A [column index](Table::with_column_order) is out of range
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let data = [Person { name: "Johnny".into(), age: 30 }];
+    /// let data_refs: Vec<&Person> = data.iter().collect();
+    /// let table: Table<'_, Person> = Table::new(&data_refs).with_header(&["Name", "Age"], None, None, None);
+    ///
+    /// println!("{}", table.to_json().unwrap()); // {"header":["Name","Age"],"rows":[["Johnny","30"]]}
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String> {
+        self.validate()?;
+
+        let header: Vec<String> = self.header_cells()?.into_iter().map(|c| c.value).collect();
+        let rows = self
+            .all_row_cells()
+            .map(|row| Ok(row?.into_iter().map(|c| c.value).collect::<Vec<_>>()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(serde_json::json!({ "header": header, "rows": rows }).to_string())
+    }
+
+    /// Compares the rows of the [Table] with the rows of another table and formats the result into a string, similar to `git diff`
+    ///
+    /// Rows are compared by the `value` of their cells, rows only present in this table are prefixed with `-` and colored red,
+    /// rows only present in `other` are prefixed with `+` and colored green, and rows present in both are left as they are.
+    /// The header of this table is used, or the header of `other` if this table has none. Panels and captions are ignored.
+    ///
+    /// ## Arguments
+    ///
+    /// * `other` - The table to compare against
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the differences between the tables
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
+    /// - [`TableError::RowLengthMismatch`]: Row length does not match first row length, or the tables have a different number of columns
+    /// - [`TableError::ColumnIndexOutOfRange`]: A [column index](Table::with_column_order) is out of range
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use tabela::{Cell, Row, Table};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// impl Row for &Person {
+    ///     fn as_row(&self) -> Vec<Cell> {
+    ///         vec![Cell::new(&self.name), Cell::new(self.age)]
+    ///     }
+    /// }
+    ///
+    /// let old = [Person { name: "Johnny".into(), age: 30 }, Person { name: "Jane".into(), age: 25 }];
+    /// let new = [Person { name: "Johnny".into(), age: 31 }, Person { name: "Jane".into(), age: 25 }];
+    /// let old_refs: Vec<&Person> = old.iter().collect();
+    /// let new_refs: Vec<&Person> = new.iter().collect();
+    /// let old_table: Table<'_, Person> = Table::new(&old_refs).with_header(&["Name", "Age"], None, None, None);
+    /// let new_table: Table<'_, Person> = Table::new(&new_refs);
+    ///
+    /// println!("{}", old_table.diff(&new_table).unwrap());
+    ///
+    /// // Output (without color characters):
+    /// //
+    /// //   Name   Age
+    /// // - Johnny 30
+    /// // + Johnny 31
+    /// //   Jane   25
+    /// ```
+    pub fn diff<'b, S>(&self, other: &Table<'b, S>) -> Result<String>
+    where
+        &'b S: Row,
+    {
+        let self_widths = self.column_widths()?;
+        let other_widths = other.column_widths()?;
+
+        if !self_widths.is_empty()
+            && !other_widths.is_empty()
+            && self_widths.len() != other_widths.len()
+        {
+            return Err(TableError::RowLengthMismatch(
+                other_widths.len(),
+                self_widths.len(),
+            ));
+        }
+
+        let mut col_widths = if self_widths.len() >= other_widths.len() {
+            self_widths
+        } else {
+            other_widths.clone()
+        };
+        for (width, other_width) in col_widths.iter_mut().zip(other_widths) {
+            *width = (*width).max(other_width);
+        }
+        col_widths.insert(0, 1);
+
+        let mut header = self.header_cells()?;
+        if header.is_empty() {
+            header = other.header_cells()?;
+        }
+
+        let self_rows = self.all_row_cells().collect::<Result<Vec<_>>>()?;
+        let other_rows = other.all_row_cells().collect::<Result<Vec<_>>>()?;
+        let self_keys: Vec<Vec<&str>> = self_rows.iter().map(|r| row_key(r)).collect();
+        let other_keys: Vec<Vec<&str>> = other_rows.iter().map(|r| row_key(r)).collect();
+
+        let mut output = String::new();
+        if !header.is_empty() {
+            let cells: Vec<Cell> = std::iter::once(Cell::new(" ")).chain(header).collect();
+            self.write_cells(&mut output, &cells, &col_widths);
+        }
+
+        for change in diff_rows(&self_keys, &other_keys) {
+            let (prefix, color, row) = match change {
+                RowChange::Removed(i) => ("-", Some(Color::Red), &self_rows[i]),
+                RowChange::Added(i) => ("+", Some(Color::Green), &other_rows[i]),
+                RowChange::Unchanged(i) => (" ", None, &self_rows[i]),
+            };
+
+            let cells: Vec<Cell> = std::iter::once(Cell::new(prefix))
+                .chain(row.iter().cloned())
+                .map(|c| match color {
+                    Some(color) => c.with_color(color),
+                    None => c,
+                })
+                .collect();
+            self.write_cells(&mut output, &cells, &col_widths);
+        }
+
+        Ok(output)
+    }
+
+    /// Writes a line of cells padded to the column widths and separated by the table separator
+    ///
+    /// ## Arguments
+    ///
+    /// * `output` - The string to write to
+    /// * `cells` - The cells to write
+    /// * `col_widths` - The width of each column
+    fn write_cells(&self, output: &mut String, cells: &[Cell], col_widths: &[usize]) {
+        for (i, cell) in cells.iter().enumerate() {
+            if i < col_widths.len() {
+                let content_width = display_width(&cell.value);
+                let padding = col_widths[i].saturating_sub(content_width);
+
+                // plain cells display as their value so there's no need to render them through `colored`
+                if cell.is_plain() {
+                    format_cell(output, cell.alignment, &cell.value, padding);
+                } else {
+                    format_cell(output, cell.alignment, &format!("{cell}"), padding);
+                }
+            } else {
+                write!(output, "{cell}").unwrap();
+            }
+
+            if i < cells.len() - 1 {
+                write!(output, "{}", self.separator).unwrap();
+            }
+        }
+
+        if self.trim_trailing && self.panel_title.is_none() {
+            let line_end = output.trim_end_matches(' ').len();
+            output.truncate(line_end);
+        }
+
+        writeln!(output).unwrap();
+    }
+
+    /// Returns the cells of a row with the [column alignments](Table::with_column_alignments) applied, in the [column order](Table::with_column_order)
+    ///
+    /// ## Arguments
+    ///
+    /// * `row` - The row to get the cells from
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::ColumnIndexOutOfRange`]: A column index is out of range for the row
+    fn row_cells(&self, row: &'a R) -> Result<Vec<Cell>> {
+        self.prepare_row(row.as_row())
+    }
+
+    /// Returns the cells of every row, the borrowed rows first and then the ones [collected from an iterator](Table::from_iter), see [`Table::row_cells`]
+    fn all_row_cells(&self) -> Box<dyn Iterator<Item = Result<Vec<Cell>>> + '_> {
+        let borrowed = self.rows.iter().map(|row| self.row_cells(*row));
+        let collected = self
+            .collected_rows
+            .iter()
+            .map(|cells| self.prepare_row(cells.clone()));
+
+        Box::new(borrowed.chain(collected))
+    }
+
+    /// Calculates the width of each column based on the widest cell in it, including the header. The last column is
+    /// widened to fit the [spanning](Cell::spanning) rows if they're wider than the other rows.
+    ///
+    /// ## Errors
+    ///
+    /// - [`TableError::HeaderLengthMismatch`]: Header length does not match row length
+    /// - [`TableError::RowLengthMismatch`]: Row length does not match first row length
+    /// - [`TableError::ColumnIndexOutOfRange`]: A column index is out of range
+    fn column_widths(&self) -> Result<Vec<usize>> {
+        let header = self.header_cells()?;
+        let mut col_widths: Vec<usize> = Vec::new();
+        if !header.is_empty() {
+            col_widths = header.iter().map(|c| display_width(&c.value)).collect();
+        }
+
+        let mut first_row_len = None;
+        let mut spanning_width = 0;
+        for row_values in self.all_row_cells() {
+            let row_values = row_values?;
+            if is_spanning(&row_values) {
+                spanning_width = spanning_width.max(display_width(&row_values[0].value));
+                continue;
+            }
+
+            let first_row_len = *first_row_len.get_or_insert(row_values.len());
+
+            if !header.is_empty() && header.len() != first_row_len {
+                return Err(TableError::HeaderLengthMismatch(
+                    header.len(),
+                    first_row_len,
+                ));
+            }
+
+            if header.is_empty() && col_widths.is_empty() && first_row_len > 0 {
+                col_widths = vec![0; first_row_len];
+            }
+
+            if row_values.len() != col_widths.len() && !header.is_empty() {
+                return Err(TableError::HeaderLengthMismatch(
+                    row_values.len(),
+                    col_widths.len(),
+                ));
+            }
+            if row_values.len() != first_row_len {
+                return Err(TableError::RowLengthMismatch(
+                    row_values.len(),
+                    first_row_len,
+                ));
+            }
+
+            for (i, value) in row_values.iter().enumerate() {
+                let cell_content_width = display_width(&value.value);
+                if i < col_widths.len() {
+                    col_widths[i] = col_widths[i].max(cell_content_width);
+                } else {
+                    col_widths.push(cell_content_width);
+                }
+            }
+        }
+
+        for (i, width) in col_widths.iter_mut().enumerate() {
+            let column = self.column_order.as_ref().map_or(i, |order| order[i]);
+            if let Some(&min_width) = self.min_widths.get(column) {
+                *width = (*width).max(min_width);
+            }
+        }
+
+        // spanning rows wider than the columns widen the last one so every line has the same width
+        let columns_width = table_width(&col_widths, &self.separator);
+        if spanning_width > columns_width {
+            match col_widths.last_mut() {
+                Some(last) => *last += spanning_width - columns_width,
+                None => col_widths.push(spanning_width),
+            }
+        }
+
+        Ok(col_widths)
+    }
+
+    /// Encloses the formatted rows in a box with the title in the top border and the caption (if any) in the bottom border
+    ///
+    /// ## Arguments
+    ///
+    /// * `body` - The formatted rows
+    /// * `title` - The title of the panel
+    /// * `width` - The width of the formatted rows
+    fn wrap_in_panel(&self, body: &str, title: &str, width: usize) -> String {
+        let title_width = display_width(title);
+        let caption_width = self.caption.as_deref().map_or(0, display_width);
+
+        // the content is padded by a space on each side, the title and caption need
+        // at least "─ " before and " ─" after them
+        let inner_width = (width + 2).max(title_width + 4).max(caption_width + 4);
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "┌─ {title} {}┐",
+            "─".repeat(inner_width - title_width - 3)
+        )
+        .unwrap();
+
+        for line in body.lines() {
+            writeln!(output, "│ {line}{} │", " ".repeat(inner_width - width - 2)).unwrap();
+        }
+
+        match &self.caption {
+            Some(caption) => writeln!(
+                output,
+                "└─ {caption} {}┘",
+                "─".repeat(inner_width - caption_width - 3)
+            ),
+            None => writeln!(output, "└{}┘", "─".repeat(inner_width)),
+        }
+        .unwrap();
+
+        output
+    }
+}
+
+/// Creates a [Table] that owns its rows, each row is turned into [cells](Cell) right away so the iterator is only
+/// consumed once, then formatting goes over the cells twice, once to calculate the column widths and once to write them.
+///
+/// The rows are implemented for references like with [`Table::new`] but the items of the iterator are owned, so they
+/// can be created on the fly. Note that the cells of every row are kept in memory until the table is dropped, which
+/// takes more memory than borrowing rows that already exist, so prefer [`Table::new`] if the data is already collected.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use tabela::{Cell, Row, Table};
+///
+/// struct Square {
+///     side: u32,
+/// }
+///
+/// impl Row for &Square {
+///     fn as_row(&self) -> Vec<Cell> {
+///         vec![Cell::new(self.side), Cell::new(self.side * self.side)]
+///     }
+/// }
+///
+/// let table: Table<'_, Square> = (1..=10).map(|side| Square { side }).collect();
+/// let table = table.with_header(&["Side", "Area"], None, None, None);
+/// println!("{}", table.format().unwrap());
+/// ```
+impl<R> FromIterator<R> for Table<'_, R>
+where
+    for<'r> &'r R: Row,
+{
+    fn from_iter<I>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+    {
+        let mut table = Table::new(&[]);
+        table.collected_rows = rows.into_iter().map(|row| (&row).as_row()).collect();
+        table
+    }
+}
+
+/// A change between two lists of rows, holding the index of the row in the list it comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowChange {
+    /// Row only present in the first list
+    Removed(usize),
+
+    /// Row only present in the second list
+    Added(usize),
+
+    /// Row present in both lists, the index is from the first list
+    Unchanged(usize),
+}
+
+/// Compares two lists of rows using their longest common subsequence and returns the changes in order, the rows they
+/// start and end with in common are skipped first so only the rows in between need the quadratic table
+///
+/// ## Arguments
+///
+/// * `old` - The keys of the first list of rows
+/// * `new` - The keys of the second list of rows
+fn diff_rows<T>(old: &[T], new: &[T]) -> Vec<RowChange>
+where
+    T: PartialEq,
+{
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_mid, new_mid) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    // lcs[i][j] is the length of the longest common subsequence of old_mid[i..] and new_mid[j..]
+    let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    changes.extend((0..prefix).map(RowChange::Unchanged));
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() && j < new_mid.len() {
+        if old_mid[i] == new_mid[j] {
+            changes.push(RowChange::Unchanged(prefix + i));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            changes.push(RowChange::Removed(prefix + i));
+            i += 1;
+        } else {
+            changes.push(RowChange::Added(prefix + j));
+            j += 1;
+        }
+    }
+
+    changes.extend((prefix + i..prefix + old_mid.len()).map(RowChange::Removed));
+    changes.extend((prefix + j..prefix + new_mid.len()).map(RowChange::Added));
+    changes.extend((old.len() - suffix..old.len()).map(RowChange::Unchanged));
+    changes
+}
+
+/// Returns the identity of a row, which is the tuple of its cell values
+///
+/// ## Arguments
+///
+/// * `row` - The cells of the row
+fn row_key(row: &[Cell]) -> Vec<&str> {
+    row.iter().map(|c| c.value.as_str()).collect()
+}
+
+/// Calculates the width of a formatted row, which is the sum of the column widths and the separators between them.
+///
+/// ## Arguments
+///
+/// * `col_widths` - The width of each column.
+/// * `separator` - The separator between the columns.
+fn table_width(col_widths: &[usize], separator: &str) -> usize {
+    let separators = col_widths.len().saturating_sub(1) * display_width(separator);
+    col_widths.iter().sum::<usize>() + separators
+}
+
+/// Calculates the width of a string in terminal columns, one grapheme cluster at a time so a sequence like 👨‍👩‍👧 or a
+/// flag counts as a single emoji.
+///
+/// ## Arguments
+///
+/// * `value` - The string to measure.
+fn display_width(value: &str) -> usize {
+    value.graphemes(true).map(UnicodeWidthStr::width).sum()
+}
+
+/// Formats a [Cell] to a string.
+///
+/// ## Arguments
+///
+/// * `output` - The string to write to.
+/// * `alignment` - The alignment of the cell.
+/// * `value` - The value of the cell.
+/// * `padding` - The padding to add to the cell.
+fn format_cell(output: &mut String, alignment: Alignment, value: &str, padding: usize) {
+    match alignment {
+        Alignment::Left => {
+            write!(output, "{value}{}", " ".repeat(padding)).unwrap();
+        }
+        Alignment::Center | Alignment::CenterRight => {
+            let left_padding = if alignment == Alignment::Center {
+                padding / 2
+            } else {
+                padding - padding / 2
+            };
+            let right_padding = padding - left_padding;
+
+            write!(
+                output,
+                "{}{value}{}",
+                " ".repeat(left_padding),
+                " ".repeat(right_padding)
+            )
+            .unwrap();
+        }
+        Alignment::Right => {
+            write!(output, "{}{value}", " ".repeat(padding)).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use handy::iter::IntoRefVec;
+
+    #[test]
+    fn test_table() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), Cell::new(self.age)]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Age"], None, None, None)
+            .with_separator("  ");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(formatted, "Name    Age\nJohnny  30 \nJane    25 \n");
+
+        // Output:
+        //
+        // Name    Age
+        // Johnny  30
+        // Jane    25
+    }
+
+    #[test]
+    fn test_table_centered_header() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![
+                    Cell::new(&self.name).with_alignment(Alignment::Right),
+                    Cell::new(self.age).with_alignment(Alignment::Center),
+                ]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Some Age"], None, None, Some(Alignment::Center))
+            .with_separator("  ");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(
+            formatted,
+            " Name   Some Age\nJohnny     30   \n  Jane     25   \n"
+        );
+
+        // Output:
+        //
+        //  Name   Some Age
+        // Johnny     30
+        //   Jane     25
+    }
+
+    #[test]
+    fn test_table_colored() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![
+                    self.name.clone().into(),
+                    Cell::new(self.age).with_color(Color::Cyan),
+                ]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Age"], None, Some(CellStyle::Bold), None)
+            .with_separator("  ");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(
+            formatted,
+            "\u{1b}[1mName\u{1b}[0m    \u{1b}[1mAge\u{1b}[0m\nJohnny  \u{1b}[36m30\u{1b}[0m \nJane    \u{1b}[36m25\u{1b}[0m \n"
+        );
+
+        // Output:
+        //
+        // Name    Age
+        // Johnny  30
+        // Jane    25
+    }
+
+    #[test]
+    fn test_table_empty_header() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), Cell::new(self.age)]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs);
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(formatted, "Johnny 30\nJane   25\n");
+
+        // Output:
+        //
+        // Johnny 30
+        // Jane   25
+    }
+
+    #[test]
+    fn test_table_empty_rows() {
+        #[derive(Debug)]
+        struct Person;
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![]
+            }
+        }
+
+        let data = [];
+        let data_refs = data.as_ref_vec();
+        let table: Table<'_, Person> = Table::new(&data_refs)
+            .with_header(&["Name", "Age"], None, None, None)
+            .with_separator(" | ");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(formatted, "Name | Age\n");
+
+        // Output:
+        //
+        // Name | Age
+    }
+
+    #[test]
+    fn test_table_with_alignment() {
+        #[derive(Debug)]
+        struct Person<'a> {
+            name: &'a str,
+            age: u8,
+            number: u32,
+        }
+
+        impl Row for &Person<'_> {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![
+                    Cell::new(self.name),
+                    Cell::new(self.age).with_alignment(Alignment::Center),
+                    Cell::new(self.number).with_alignment(Alignment::Right),
+                ]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny",
+                age: 30,
+                number: 1,
+            },
+            Person {
+                name: "Jane",
+                age: 25,
+                number: 2,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(
+                &["Person's name", "Person's Age", "Number"],
+                None,
+                None,
+                None,
+            )
+            .with_separator(" | ");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(formatted, "Person's name | Person's Age | Number\nJohnny        |      30      |      1\nJane          |      25      |      2\n");
+
+        // Output:
+        //
+        // Person's name | Person's Age | Number
+        // Johnny        |      30      |      1
+        // Jane          |      25      |      2
+    }
+
+    #[test]
+    fn test_table_center_right() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            alignment: Alignment,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![Cell::new(&self.name).with_alignment(self.alignment)]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                alignment: Alignment::Left,
+            },
+            Person {
+                name: "Bob".into(),
+                alignment: Alignment::Center,
+            },
+            Person {
+                name: "Bob".into(),
+                alignment: Alignment::CenterRight,
+            },
+            Person {
+                name: "Jane".into(),
+                alignment: Alignment::CenterRight,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs).with_separator("|");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(formatted, "Johnny\n Bob  \n  Bob \n Jane \n");
+
+        // Output:
+        //
+        // Johnny
+        //  Bob
+        //   Bob
+        //  Jane
+    }
+
+    #[test]
+    fn test_table_trim_trailing() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+            alignment: Alignment,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![
+                    self.name.clone().into(),
+                    Cell::new(self.age).with_alignment(self.alignment),
+                ]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+                alignment: Alignment::Left,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+                alignment: Alignment::Center,
+            },
+            Person {
+                name: "Joe".into(),
+                age: 7,
+                alignment: Alignment::Right,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Some Age"], None, None, None)
+            .with_separator("  ")
+            .trim_trailing(true);
+        let formatted = dbg!(table).format().unwrap();
+        assert!(formatted.lines().all(|line| !line.ends_with(' ')));
+        assert_eq!(
+            formatted,
+            "Name    Some Age\nJohnny  30\nJane       25\nJoe            7\n"
+        );
+
+        let untrimmed = Table::new(&data_refs)
+            .with_header(&["Name", "Some Age"], None, None, None)
+            .with_separator("  ")
+            .format()
+            .unwrap();
+        assert_eq!(
+            untrimmed,
+            "Name    Some Age\nJohnny  30      \nJane       25   \nJoe            7\n"
+        );
+
+        // Output:
+        //
+        // Name    Some Age
+        // Johnny  30
+        // Jane       25
+        // Joe            7
+    }
+
+    #[test]
+    fn test_table_column_alignments() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+            city: String,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![
+                    self.name.clone().into(),
+                    Cell::new(self.age),
+                    Cell::new(&self.city).with_alignment(Alignment::Center),
+                ]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+                city: "Lisbon".into(),
+            },
+            Person {
+                name: "Jane".into(),
+                age: 5,
+                city: "Porto".into(),
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Age", "City"], None, None, None)
+            .with_separator(" | ")
+            .with_column_alignments(&[Alignment::Left, Alignment::Right, Alignment::Right]);
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(
+            formatted,
+            "Name   | Age |   City\nJohnny |  30 | Lisbon\nJane   |   5 | Porto \n"
+        );
+
+        // Output:
+        //
+        // Name   | Age |   City
+        // Johnny |  30 | Lisbon
+        // Jane   |   5 | Porto
+    }
+
+    #[test]
+    fn test_table_min_widths() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), Cell::new(self.age)]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Age"], None, None, None)
+            .with_separator("|")
+            .with_min_widths(&[2, 10]);
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(
+            formatted,
+            "Name  |Age       \nJohnny|30        \nJane  |25        \n"
+        );
+
+        let table = Table::new(&data_refs)
+            .with_separator("|")
+            .with_column_order(&[1, 0])
+            .with_min_widths(&[0, 10]);
+        assert_eq!(
+            table.format().unwrap(),
+            "30        |Johnny\n25        |Jane  \n"
+        );
+
+        // Output:
+        //
+        // Name  |Age
+        // Johnny|30
+        // Jane  |25
+    }
+
+    #[test]
+    fn test_table_grapheme_clusters() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), Cell::new(self.age)]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "👨‍👩‍👧 Family".into(),
+                age: 30,
+            },
+            Person {
+                name: "🇧🇷 Brasil".into(),
+                age: 25,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 20,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Age"], None, None, None)
+            .with_separator("|");
+        assert_eq!(
+            table.format().unwrap(),
+            "Name     |Age\n👨‍👩‍👧 Family|30 \n🇧🇷 Brasil|25 \nJane     |20 \n"
+        );
+
+        // Output:
+        //
+        // Name     |Age
+        // 👨‍👩‍👧 Family|30
+        // 🇧🇷 Brasil|25
+        // Jane     |20
+    }
+
+    #[test]
+    fn test_table_panel() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), Cell::new(self.age)]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Age"], None, None, None)
+            .with_separator("  ")
+            .as_panel("People");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(
+            formatted,
+            "┌─ People ────┐\n│ Name    Age │\n│ Johnny  30  │\n│ Jane    25  │\n└─────────────┘\n"
+        );
+
+        let table = Table::new(&data_refs)
+            .with_caption("2 people in total")
+            .as_panel("People");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(
+            formatted,
+            "┌─ People ────────────┐\n│ Johnny 30           │\n│ Jane   25           │\n└─ 2 people in total ─┘\n"
+        );
+
+        // Output:
+        //
+        // ┌─ People ────┐
+        // │ Name    Age │
+        // │ Johnny  30  │
+        // │ Jane    25  │
+        // └─────────────┘
+    }
+
+    #[test]
+    fn test_table_caption() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into()]
+            }
+        }
+
+        let data = [Person {
+            name: "Johnny".into(),
+        }];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs).with_caption("1 person");
+        let formatted = dbg!(table).format().unwrap();
+        assert_eq!(formatted, "Johnny\n1 person\n");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_table_to_records() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), Cell::new(self.age)]
+            }
+        }
+
+        let data = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs).with_header(&["Name", "Age"], None, None, None);
+        let records = dbg!(&table).to_records().unwrap();
+        assert_eq!(
+            serde_json::Value::from(
+                records
+                    .into_iter()
+                    .map(serde_json::Value::Object)
+                    .collect::<Vec<_>>()
+            ),
+            serde_json::json!([
+                {"Name": "Johnny", "Age": "30"},
+                {"Name": "Jane", "Age": "25"},
+            ])
+        );
+
+        let table = Table::new(&data_refs);
+        assert_eq!(table.to_records(), Err(TableError::MissingHeader));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_table_to_json() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
 
         impl Row for &Person {
             fn as_row(&self) -> Vec<Cell> {
-                vec![self.name.clone().into(), Cell::new(self.age)]
+                vec![
+                    Cell::new(&self.name).with_color(Color::Green),
+                    Cell::new(self.age).with_style(CellStyle::Bold),
+                ]
             }
         }
 
@@ -716,32 +2351,147 @@ mod tests {
             },
         ];
         let data_refs = data.as_ref_vec();
-        let table = Table::new(&data_refs)
-            .with_header(&["Name", "Age"], None, None, None)
-            .with_separator("  ");
-        let formatted = dbg!(table).format().unwrap();
-        assert_eq!(formatted, "Name    Age\nJohnny  30 \nJane    25 \n");
+        let table = Table::new(&data_refs).with_header(&["Name", "Age"], None, None, None);
+        let json = dbg!(&table).to_json().unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!({
+                "header": ["Name", "Age"],
+                "rows": [["Johnny", "30"], ["Jane", "25"]],
+            })
+        );
+
+        let table = Table::new(&data_refs);
+        assert_eq!(
+            table.to_json().unwrap(),
+            r#"{"header":[],"rows":[["Johnny","30"],["Jane","25"]]}"#
+        );
+
+        let table = Table::new(&data_refs).with_header(&["Name"], None, None, None);
+        assert_eq!(table.to_json(), Err(TableError::HeaderLengthMismatch(1, 2)));
+    }
+
+    #[test]
+    fn test_table_diff() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: u8,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), Cell::new(self.age)]
+            }
+        }
+
+        let old = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Jane".into(),
+                age: 25,
+            },
+            Person {
+                name: "Bob".into(),
+                age: 40,
+            },
+        ];
+        let new = [
+            Person {
+                name: "Johnny".into(),
+                age: 30,
+            },
+            Person {
+                name: "Bob".into(),
+                age: 40,
+            },
+            Person {
+                name: "Alice".into(),
+                age: 22,
+            },
+        ];
+        let old_refs = old.as_ref_vec();
+        let new_refs = new.as_ref_vec();
+        let old_table = Table::new(&old_refs).with_header(&["Name", "Age"], None, None, None);
+        let new_table = Table::new(&new_refs);
+        let formatted = old_table.diff(&new_table).unwrap();
+        // built with `colored` so it only has colors when they're enabled
+        assert_eq!(
+            formatted,
+            format!(
+                "  Name   Age\n  Johnny 30 \n{} {}   {} \n  Bob    40 \n{} {}  {} \n",
+                "-".red(),
+                "Jane".red(),
+                "25".red(),
+                "+".green(),
+                "Alice".green(),
+                "22".green()
+            )
+        );
 
         // Output:
         //
-        // Name    Age
-        // Johnny  30
-        // Jane    25
+        //   Name   Age
+        //   Johnny 30
+        // - Jane   25
+        //   Bob    40
+        // + Alice  22
     }
 
     #[test]
-    fn test_table_centered_header() {
+    fn test_diff_rows() {
+        use RowChange::{Added, Removed, Unchanged};
+
+        assert_eq!(
+            diff_rows(&[1, 2, 3, 4, 5], &[1, 2, 6, 4, 5]),
+            vec![
+                Unchanged(0),
+                Unchanged(1),
+                Removed(2),
+                Added(2),
+                Unchanged(3),
+                Unchanged(4)
+            ]
+        );
+        assert_eq!(
+            diff_rows(&[1, 2, 3], &[1, 2, 3]),
+            vec![Unchanged(0), Unchanged(1), Unchanged(2)]
+        );
+        assert_eq!(
+            diff_rows(&[1, 2], &[0, 1, 2, 3]),
+            vec![Added(0), Unchanged(0), Unchanged(1), Added(3)]
+        );
+        assert_eq!(
+            diff_rows(&[1, 2, 3, 1], &[1, 3, 2, 1]),
+            vec![
+                Unchanged(0),
+                Removed(1),
+                Unchanged(2),
+                Added(2),
+                Unchanged(3)
+            ]
+        );
+        assert_eq!(diff_rows::<i32>(&[], &[]), vec![]);
+    }
+
+    #[test]
+    fn test_table_column_order() {
         #[derive(Debug)]
         struct Person {
             name: String,
             age: u8,
+            city: String,
         }
 
         impl Row for &Person {
             fn as_row(&self) -> Vec<Cell> {
                 vec![
-                    Cell::new(&self.name).with_alignment(Alignment::Right),
-                    Cell::new(self.age).with_alignment(Alignment::Center),
+                    Cell::new(&self.name),
+                    Cell::new(self.age),
+                    Cell::new(&self.city),
                 ]
             }
         }
@@ -750,31 +2500,41 @@ mod tests {
             Person {
                 name: "Johnny".into(),
                 age: 30,
+                city: "Lisbon".into(),
             },
             Person {
                 name: "Jane".into(),
                 age: 25,
+                city: "Porto".into(),
             },
         ];
         let data_refs = data.as_ref_vec();
-        let table = Table::new(&data_refs)
-            .with_header(&["Name", "Some Age"], None, None, Some(Alignment::Center))
-            .with_separator("  ");
-        let formatted = dbg!(table).format().unwrap();
+
+        let reversed = Table::new(&data_refs)
+            .with_header(&["Name", "Age", "City"], None, None, None)
+            .with_column_order(&[1, 0]);
         assert_eq!(
-            formatted,
-            " Name   Some Age\nJohnny     30   \n  Jane     25   \n"
+            reversed.format().unwrap(),
+            "Age Name  \n30  Johnny\n25  Jane  \n"
         );
 
-        // Output:
-        //
-        //  Name   Some Age
-        // Johnny     30
-        //   Jane     25
+        let dropped = Table::new(&data_refs)
+            .with_header(&["Name", "Age", "City"], None, None, None)
+            .with_column_order(&[0, 2]);
+        assert_eq!(
+            dropped.format().unwrap(),
+            "Name   City  \nJohnny Lisbon\nJane   Porto \n"
+        );
+
+        let out_of_range = Table::new(&data_refs).with_column_order(&[0, 3]);
+        assert_eq!(
+            out_of_range.format(),
+            Err(TableError::ColumnIndexOutOfRange(3, 3))
+        );
     }
 
     #[test]
-    fn test_table_colored() {
+    fn test_table_header_cells() {
         #[derive(Debug)]
         struct Person {
             name: String,
@@ -783,10 +2543,7 @@ mod tests {
 
         impl Row for &Person {
             fn as_row(&self) -> Vec<Cell> {
-                vec![
-                    self.name.clone().into(),
-                    Cell::new(self.age).with_color(Color::Cyan),
-                ]
+                vec![self.name.clone().into(), Cell::new(self.age)]
             }
         }
 
@@ -801,24 +2558,38 @@ mod tests {
             },
         ];
         let data_refs = data.as_ref_vec();
-        let table = Table::new(&data_refs)
-            .with_header(&["Name", "Age"], None, Some(CellStyle::Bold), None)
-            .with_separator("  ");
+        let table = Table::new(&data_refs).with_header_cells(vec![
+            Cell::new("Name").with_color(Color::Red),
+            Cell::new("Age")
+                .with_color(Color::Green)
+                .with_alignment(Alignment::Right),
+        ]);
         let formatted = dbg!(table).format().unwrap();
+        // built with `colored` so it only has colors when they're enabled
         assert_eq!(
             formatted,
-            "\u{1b}[1mName\u{1b}[0m    \u{1b}[1mAge\u{1b}[0m\nJohnny  \u{1b}[36m30\u{1b}[0m \nJane    \u{1b}[36m25\u{1b}[0m \n"
+            format!(
+                "{}   {}\nJohnny 30 \nJane   25 \n",
+                "Name".red(),
+                "Age".green()
+            )
         );
 
+        let table = Table::new(&data_refs).with_header_cells(vec![Cell::new("Name")]);
+        assert!(matches!(
+            table.format(),
+            Err(TableError::HeaderLengthMismatch(1, 2))
+        ));
+
         // Output:
         //
-        // Name    Age
-        // Johnny  30
-        // Jane    25
+        // Name   Age
+        // Johnny 30
+        // Jane   25
     }
 
     #[test]
-    fn test_table_empty_header() {
+    fn test_table_total_width() {
         #[derive(Debug)]
         struct Person {
             name: String,
@@ -842,88 +2613,279 @@ mod tests {
             },
         ];
         let data_refs = data.as_ref_vec();
-        let table = Table::new(&data_refs);
-        let formatted = dbg!(table).format().unwrap();
-        assert_eq!(formatted, "Johnny 30\nJane   25\n");
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Some Age"], None, None, None)
+            .with_separator(" │ ");
+        assert_eq!(dbg!(&table).total_width().unwrap(), 6 + 3 + 8);
+
+        let formatted = table.format().unwrap();
+        assert!(formatted
+            .lines()
+            .all(|line| UnicodeWidthStr::width(line) == table.total_width().unwrap()));
+
+        let table = Table::new(&data_refs).with_header(&["Name"], None, None, None);
+        assert!(matches!(
+            table.total_width(),
+            Err(TableError::HeaderLengthMismatch(1, 2))
+        ));
 
         // Output:
         //
-        // Johnny 30
-        // Jane   25
+        // Name   │ Some Age
+        // Johnny │ 30
+        // Jane   │ 25
     }
 
     #[test]
-    fn test_table_empty_rows() {
+    fn test_table_spanning_row() {
         #[derive(Debug)]
-        struct Person;
+        struct Entry {
+            name: String,
+            age: Option<u8>,
+        }
 
-        impl Row for &Person {
+        impl Row for &Entry {
             fn as_row(&self) -> Vec<Cell> {
-                vec![]
+                match self.age {
+                    Some(age) => vec![self.name.clone().into(), Cell::new(age)],
+                    None => vec![Cell::spanning(format!("── {} ──", self.name))
+                        .with_alignment(Alignment::Center)],
+                }
             }
         }
 
-        let data = [];
+        let data = [
+            Entry {
+                name: "Adults".into(),
+                age: None,
+            },
+            Entry {
+                name: "Johnny".into(),
+                age: Some(30),
+            },
+            Entry {
+                name: "Kids".into(),
+                age: None,
+            },
+            Entry {
+                name: "Jane".into(),
+                age: Some(8),
+            },
+        ];
         let data_refs = data.as_ref_vec();
-        let table: Table<'_, Person> = Table::new(&data_refs)
+        let table = Table::new(&data_refs)
+            .with_header(&["Name", "Some Age"], None, None, None)
+            .with_column_order(&[1, 0]);
+        let formatted = dbg!(&table).format().unwrap();
+        assert_eq!(
+            formatted,
+            "Some Age Name  \n ── Adults ──  \n30       Johnny\n  ── Kids ──   \n8        Jane  \n"
+        );
+        assert!(formatted
+            .lines()
+            .all(|line| UnicodeWidthStr::width(line) == table.total_width().unwrap()));
+
+        // Output:
+        //
+        // Some Age Name
+        //  ── Adults ──
+        // 30       Johnny
+        //   ── Kids ──
+        // 8        Jane
+    }
+
+    #[test]
+    fn test_table_panel_spanning_row() {
+        #[derive(Debug)]
+        struct Entry {
+            name: String,
+            age: Option<u8>,
+        }
+
+        impl Row for &Entry {
+            fn as_row(&self) -> Vec<Cell> {
+                match self.age {
+                    Some(age) => vec![self.name.clone().into(), Cell::new(age)],
+                    None => vec![Cell::spanning(&self.name)],
+                }
+            }
+        }
+
+        let data = [
+            Entry {
+                name: "Adults and teenagers".into(),
+                age: None,
+            },
+            Entry {
+                name: "Johnny".into(),
+                age: Some(30),
+            },
+        ];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
             .with_header(&["Name", "Age"], None, None, None)
-            .with_separator(" | ");
+            .as_panel("People");
+        let formatted = dbg!(&table).format().unwrap();
+        assert_eq!(
+            formatted,
+            "┌─ People ─────────────┐\n│ Name   Age           │\n│ Adults and teenagers │\n│ Johnny 30            │\n└──────────────────────┘\n"
+        );
+        assert_eq!(table.total_width().unwrap(), 20);
+
+        // only spanning rows
+        let data = [Entry {
+            name: "Adults".into(),
+            age: None,
+        }];
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs).as_panel("People");
+        assert_eq!(
+            table.format().unwrap(),
+            "┌─ People ─┐\n│ Adults   │\n└──────────┘\n"
+        );
+
+        // Output:
+        //
+        // ┌─ People ─────────────┐
+        // │ Name   Age           │
+        // │ Adults and teenagers │
+        // │ Johnny 30            │
+        // └──────────────────────┘
+    }
+
+    #[test]
+    fn test_table_repeat_header() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                vec![self.name.clone().into()]
+            }
+        }
+
+        let data: Vec<Person> = ["Johnny", "Jane", "Joe", "Jill", "Jack"]
+            .into_iter()
+            .map(|name| Person { name: name.into() })
+            .collect();
+        let data_refs = data.as_ref_vec();
+        let table = Table::new(&data_refs)
+            .with_header(&["Name"], None, Some(CellStyle::Bold), None)
+            .repeat_header_every(2);
         let formatted = dbg!(table).format().unwrap();
-        assert_eq!(formatted, "Name | Age\n");
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines.len(), 8);
+
+        let header_lines: Vec<usize> = (0..lines.len()).filter(|&i| lines[i] == lines[0]).collect();
+        assert_eq!(header_lines, [0, 3, 6]);
+
+        let table = Table::new(&data_refs[..4])
+            .with_header(&["Name"], None, None, None)
+            .repeat_header_every(2);
+        assert_eq!(
+            table.format().unwrap(),
+            "Name  \nJohnny\nJane  \nName  \nJoe   \nJill  \n"
+        );
 
         // Output:
         //
-        // Name | Age
+        // Name
+        // Johnny
+        // Jane
+        // Name
+        // Joe
+        // Jill
+        // Name
+        // Jack
     }
 
     #[test]
-    fn test_table_with_alignment() {
+    fn test_table_from_iter() {
         #[derive(Debug)]
-        struct Person<'a> {
-            name: &'a str,
-            age: u8,
-            number: u32,
+        struct Square {
+            side: u32,
         }
 
-        impl Row for &Person<'_> {
+        impl Row for &Square {
             fn as_row(&self) -> Vec<Cell> {
                 vec![
-                    Cell::new(self.name),
-                    Cell::new(self.age).with_alignment(Alignment::Center),
-                    Cell::new(self.number).with_alignment(Alignment::Right),
+                    Cell::new(self.side),
+                    Cell::new(self.side * self.side).with_alignment(Alignment::Right),
                 ]
             }
         }
 
+        let table: Table<'_, Square> = (0..100).map(|side| Square { side }).collect();
+        let table = table.with_header(&["Side", "Area"], None, None, None);
+        let formatted = dbg!(&table).format().unwrap();
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines.len(), 101);
+        assert_eq!(lines[0], "Side Area");
+        assert_eq!(lines[1], "0       0");
+        assert_eq!(lines[100], "99   9801");
+        assert_eq!(table.validate(), Ok(()));
+
+        let table: Table<'_, Square> = (1..=3).map(|side| Square { side }).collect();
+        assert_eq!(table.format().unwrap(), "1 1\n2 4\n3 9\n");
+
+        let empty: Table<'_, Square> = std::iter::empty().collect();
+        assert_eq!(empty.format().unwrap(), "");
+    }
+
+    #[test]
+    fn test_table_validate() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+            age: Option<u8>,
+        }
+
+        impl Row for &Person {
+            fn as_row(&self) -> Vec<Cell> {
+                let mut cells = vec![self.name.clone().into()];
+                if let Some(age) = self.age {
+                    cells.push(Cell::new(age));
+                }
+                cells
+            }
+        }
+
         let data = [
             Person {
-                name: "Johnny",
-                age: 30,
-                number: 1,
+                name: "Johnny".into(),
+                age: Some(30),
             },
             Person {
-                name: "Jane",
-                age: 25,
-                number: 2,
+                name: "Jane".into(),
+                age: None,
             },
         ];
         let data_refs = data.as_ref_vec();
-        let table = Table::new(&data_refs)
-            .with_header(
-                &["Person's name", "Person's Age", "Number"],
-                None,
-                None,
-                None,
-            )
-            .with_separator(" | ");
-        let formatted = dbg!(table).format().unwrap();
-        assert_eq!(formatted, "Person's name | Person's Age | Number\nJohnny        |      30      |      1\nJane          |      25      |      2\n");
+        let (good_refs, bad_refs) = (&data_refs[..1], &data_refs[..]);
 
-        // Output:
-        //
-        // Person's name | Person's Age | Number
-        // Johnny        |      30      |      1
-        // Jane          |      25      |      2
+        let table = Table::new(good_refs).with_header(&["Name", "Age"], None, None, None);
+        assert_eq!(table.validate(), Ok(()));
+
+        let table: Table<'_, Person> =
+            Table::new(&[]).with_header(&["Name", "Age", "Extra"], None, None, None);
+        assert_eq!(table.validate(), Ok(()));
+
+        let table = Table::new(good_refs).with_header(&["Name"], None, None, None);
+        assert_eq!(
+            table.validate(),
+            Err(TableError::HeaderLengthMismatch(1, 2))
+        );
+
+        let table = Table::new(bad_refs);
+        assert_eq!(table.validate(), Err(TableError::RowLengthMismatch(1, 2)));
+
+        let table = Table::new(good_refs).with_column_order(&[0, 2]);
+        assert_eq!(
+            table.validate(),
+            Err(TableError::ColumnIndexOutOfRange(2, 2))
+        );
     }
 
     #[test]